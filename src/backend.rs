@@ -0,0 +1,228 @@
+//! Boolean operations on offset results.
+//!
+//! Offsets are computed by merging and subtracting simple pieces, so the library computing these
+//! boolean operations can be chosen through [`OffsetOptions::with_backend`].
+
+#[cfg(feature = "clipper")]
+use crate::extent::Extent;
+use crate::OffsetError;
+use geo_types::CoordFloat;
+use std::sync::Arc;
+
+#[cfg(not(any(feature = "clipper", feature = "pure-rust")))]
+compile_error!("either the `clipper` or the `pure-rust` feature must be enabled");
+
+/// Computes the boolean operations needed by offsets.
+///
+/// Polygons passed to backends may overlap themselves and each other.
+/// Backends must fill areas covered by a non-zero winding number.
+///
+/// ```
+/// # use geo_offset::{Offset, OffsetBackend, OffsetError, OffsetOptions};
+/// # use geo_types::{MultiPolygon, Point};
+/// /// Counts the operations computed by another backend.
+/// #[derive(Debug, Default)]
+/// struct Counting<B> {
+///     backend: B,
+///     count: std::sync::atomic::AtomicUsize,
+/// }
+///
+/// impl<B: OffsetBackend<f64>> OffsetBackend<f64> for Counting<B> {
+///     fn union(&self, subject: &MultiPolygon, clip: &MultiPolygon, factor: f64) -> Result<MultiPolygon, OffsetError> {
+///         self.count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+///         self.backend.union(subject, clip, factor)
+///     }
+///
+///     fn difference(&self, subject: &MultiPolygon, clip: &MultiPolygon, factor: f64) -> Result<MultiPolygon, OffsetError> {
+///         self.count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+///         self.backend.difference(subject, clip, factor)
+///     }
+/// }
+///
+/// let options = OffsetOptions::new().with_backend(Counting {
+///     backend: geo_offset::default_backend(),
+///     count: Default::default(),
+/// });
+/// let points = geo_types::MultiPoint(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0)]);
+/// let points_with_offset = points.offset_with_options(1.0, &options)?;
+/// assert_eq!(points_with_offset.0.len(), 1);
+/// # Ok::<(), OffsetError>(())
+/// ```
+pub trait OffsetBackend<F: CoordFloat>: std::fmt::Debug + Send + Sync {
+    /// Returns the area covered by `subject` or `clip`.
+    ///
+    /// Backends working on integer coordinates multiply coordinates by `factor` beforehand.
+    /// Failures are reported as [`OffsetError::BackendFailure`].
+    fn union(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+        factor: F,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError>;
+
+    /// Returns the area covered by `subject` but not by `clip`.
+    ///
+    /// Backends working on integer coordinates multiply coordinates by `factor` beforehand.
+    /// Failures are reported as [`OffsetError::BackendFailure`].
+    fn difference(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+        factor: F,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError>;
+}
+
+impl<F: CoordFloat, B: OffsetBackend<F> + ?Sized> OffsetBackend<F> for Arc<B> {
+    fn union(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+        factor: F,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        (**self).union(subject, clip, factor)
+    }
+
+    fn difference(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+        factor: F,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        (**self).difference(subject, clip, factor)
+    }
+}
+
+/// Returns the backend used when none is set in the options.
+///
+/// This is [`ClipperBackend`] unless the `pure-rust` feature is enabled, in which case it is [`GeoBackend`].
+pub fn default_backend<F: CoordFloat>() -> Arc<dyn OffsetBackend<F>> {
+    #[cfg(not(feature = "pure-rust"))]
+    let backend = Arc::new(ClipperBackend);
+    #[cfg(feature = "pure-rust")]
+    let backend = Arc::new(GeoBackend);
+    backend
+}
+
+/// Computes boolean operations with the Clipper C++ library.
+///
+/// Coordinates are snapped to `1 / factor` units.
+#[cfg(feature = "clipper")]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ClipperBackend;
+
+#[cfg(feature = "clipper")]
+impl<F: CoordFloat> OffsetBackend<F> for ClipperBackend {
+    fn union(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+        factor: F,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        check_clipper_range(subject.magnitude().max(clip.magnitude()), factor)?;
+        Ok(geo_clipper::Clipper::union(subject, clip, factor))
+    }
+
+    fn difference(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+        factor: F,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        check_clipper_range(subject.magnitude().max(clip.magnitude()), factor)?;
+        Ok(geo_clipper::Clipper::difference(subject, clip, factor))
+    }
+}
+
+/// Returns an error if coordinates up to `magnitude` leave the range of Clipper once multiplied by `factor`.
+///
+/// Clipper panics or aborts on such coordinates instead of reporting them.
+#[cfg(feature = "clipper")]
+pub(crate) fn check_clipper_range<F: CoordFloat>(
+    magnitude: F,
+    factor: F,
+) -> Result<(), OffsetError> {
+    let scaled = (magnitude * factor.abs()).to_f64().unwrap_or(f64::INFINITY);
+    if scaled <= crate::options::CLIPPER_HI_RANGE {
+        Ok(())
+    } else {
+        Err(OffsetError::BackendFailure(format!(
+            "coordinates scaled to {:e} are outside the range of Clipper",
+            scaled
+        )))
+    }
+}
+
+/// Computes boolean operations with the `geo` crate.
+///
+/// Coordinates are converted to `f64` and are not snapped, so the factor is ignored.
+#[cfg(feature = "pure-rust")]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct GeoBackend;
+
+#[cfg(feature = "pure-rust")]
+impl<F: CoordFloat> OffsetBackend<F> for GeoBackend {
+    fn union(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+        _factor: F,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        use geo::BooleanOps;
+        catch_failure(|| {
+            from_f64(
+                to_f64(subject).union_with_fill_rule(
+                    &to_f64(clip),
+                    geo::algorithm::bool_ops::FillRule::NonZero,
+                ),
+            )
+        })
+    }
+
+    fn difference(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+        _factor: F,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        use geo::BooleanOps;
+        catch_failure(|| {
+            from_f64(to_f64(subject).difference_with_fill_rule(
+                &to_f64(clip),
+                geo::algorithm::bool_ops::FillRule::NonZero,
+            ))
+        })
+    }
+}
+
+/// Runs `operation`, turning panics of the `geo` crate on degenerate input into errors.
+#[cfg(feature = "pure-rust")]
+fn catch_failure<T>(operation: impl FnOnce() -> T) -> Result<T, OffsetError> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(operation)).map_err(|payload| {
+        let reason = match payload.downcast::<String>() {
+            Ok(reason) => *reason,
+            Err(payload) => payload
+                .downcast_ref::<&str>()
+                .map_or("unknown reason", |reason| reason)
+                .to_string(),
+        };
+        OffsetError::BackendFailure(reason)
+    })
+}
+
+#[cfg(feature = "pure-rust")]
+fn to_f64<F: CoordFloat>(multi_polygon: &geo_types::MultiPolygon<F>) -> geo::MultiPolygon<f64> {
+    use geo::MapCoords;
+    multi_polygon.map_coords(|coord| geo::Coord {
+        x: coord.x.to_f64().unwrap(),
+        y: coord.y.to_f64().unwrap(),
+    })
+}
+
+#[cfg(feature = "pure-rust")]
+fn from_f64<F: CoordFloat>(multi_polygon: geo::MultiPolygon<f64>) -> geo_types::MultiPolygon<F> {
+    use geo::MapCoords;
+    multi_polygon.map_coords(|coord| geo_types::Coord {
+        x: F::from(coord.x).unwrap(),
+        y: F::from(coord.y).unwrap(),
+    })
+}
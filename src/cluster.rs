@@ -0,0 +1,92 @@
+use geo_types::CoordFloat;
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+
+/// Groups polygons whose bounding boxes overlap, directly or through other polygons.
+///
+/// Returns the indices of the members of each group, in order of their first member.
+/// Empty polygons are left out.
+pub(crate) fn overlapping_clusters<F: CoordFloat>(
+    parts: &[geo_types::MultiPolygon<F>],
+) -> Vec<Vec<usize>> {
+    let envelopes: Vec<_> = parts.iter().map(envelope).collect();
+    let tree = RTree::bulk_load(
+        envelopes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, envelope)| {
+                envelope.map(|envelope| GeomWithData::new(Rectangle::from_aabb(envelope), index))
+            })
+            .collect(),
+    );
+
+    let mut roots: Vec<_> = (0..parts.len()).collect();
+    for (index, envelope) in envelopes.iter().enumerate() {
+        let envelope = match envelope {
+            Some(envelope) => envelope,
+            None => continue,
+        };
+        for neighbour in tree.locate_in_envelope_intersecting(envelope) {
+            let root = find_root(&mut roots, index);
+            let neighbour_root = find_root(&mut roots, neighbour.data);
+            roots[root.max(neighbour_root)] = root.min(neighbour_root);
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut cluster_indices: Vec<Option<usize>> = vec![None; parts.len()];
+    for index in (0..parts.len()).filter(|&index| envelopes[index].is_some()) {
+        let root = find_root(&mut roots, index);
+        match cluster_indices[root] {
+            Some(cluster_index) => clusters[cluster_index].push(index),
+            None => {
+                cluster_indices[root] = Some(clusters.len());
+                clusters.push(vec![index]);
+            }
+        }
+    }
+    clusters
+}
+
+/// Returns the representative of the group containing `index`, compressing the path to it.
+fn find_root(roots: &mut [usize], mut index: usize) -> usize {
+    while roots[index] != index {
+        roots[index] = roots[roots[index]];
+        index = roots[index];
+    }
+    index
+}
+
+/// Returns the bounding box of the exteriors, or `None` if there are no coordinates.
+///
+/// The box is converted to `f64` so that the tree does not depend on the coordinate type,
+/// rounding never separates two boxes that touch.
+fn envelope<F: CoordFloat>(multi_polygon: &geo_types::MultiPolygon<F>) -> Option<AABB<[f64; 2]>> {
+    let mut coords = multi_polygon
+        .0
+        .iter()
+        .flat_map(|polygon| polygon.exterior().0.iter());
+    let first = coords.next()?;
+    let (min, max) = coords.fold((*first, *first), |(min, max), coord| {
+        (
+            geo_types::Coord {
+                x: min.x.min(coord.x),
+                y: min.y.min(coord.y),
+            },
+            geo_types::Coord {
+                x: max.x.max(coord.x),
+                y: max.y.max(coord.y),
+            },
+        )
+    });
+    Some(AABB::from_corners(
+        [
+            min.x.to_f64().unwrap_or(f64::NEG_INFINITY),
+            min.y.to_f64().unwrap_or(f64::NEG_INFINITY),
+        ],
+        [
+            max.x.to_f64().unwrap_or(f64::INFINITY),
+            max.y.to_f64().unwrap_or(f64::INFINITY),
+        ],
+    ))
+}
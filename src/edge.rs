@@ -9,6 +9,16 @@ pub enum EdgeError {
     VerticesOverlap,
 }
 
+impl std::fmt::Display for EdgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VerticesOverlap => write!(f, "the vertices of the edge overlap"),
+        }
+    }
+}
+
+impl std::error::Error for EdgeError {}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Edge<F: CoordFloat> {
     pub current: Point<F>,
@@ -0,0 +1,239 @@
+use crate::{Location, OffsetError};
+use geo_types::CoordFloat;
+
+/// Computes how far from the origin the coordinates of a geometry reach.
+pub(crate) trait Extent<F: CoordFloat> {
+    /// Returns the largest absolute value among all coordinates, or zero for empty geometries.
+    ///
+    /// The magnitude is infinite if any coordinate is not finite.
+    fn magnitude(&self) -> F;
+
+    /// Returns the path to the first coordinate which is not finite, if any.
+    fn non_finite_path(&self) -> Option<Vec<Location>>;
+
+    /// Returns the magnitude of the members whose coordinates are all finite.
+    fn finite_magnitude(&self) -> F {
+        let magnitude = self.magnitude();
+        if magnitude.is_finite() {
+            magnitude
+        } else {
+            F::zero()
+        }
+    }
+}
+
+/// Returns the magnitude of `geometry`, or an error if it cannot be offset by `distance`.
+pub(crate) fn checked_magnitude<F: CoordFloat, G: Extent<F> + ?Sized>(
+    geometry: &G,
+    distance: F,
+) -> Result<F, OffsetError> {
+    if !distance.is_finite() {
+        return Err(OffsetError::InvalidDistance);
+    }
+    let magnitude = geometry.magnitude();
+    if magnitude.is_finite() {
+        Ok(magnitude)
+    } else {
+        let path = geometry.non_finite_path().unwrap_or_default();
+        Err(OffsetError::NonFiniteCoordinate.within(path))
+    }
+}
+
+pub(crate) fn coords_magnitude<'a, F: CoordFloat + 'a>(
+    coords: impl IntoIterator<Item = &'a geo_types::Coord<F>>,
+) -> F {
+    coords.into_iter().fold(F::zero(), |magnitude, coord| {
+        if coord.x.is_finite() && coord.y.is_finite() {
+            magnitude.max(coord.x.abs()).max(coord.y.abs())
+        } else {
+            F::infinity()
+        }
+    })
+}
+
+fn coords_non_finite_path<'a, F: CoordFloat + 'a>(
+    coords: impl IntoIterator<Item = &'a geo_types::Coord<F>>,
+) -> Option<Vec<Location>> {
+    coords
+        .into_iter()
+        .position(|coord| !(coord.x.is_finite() && coord.y.is_finite()))
+        .map(|index| vec![Location::Vertex(index)])
+}
+
+fn members_non_finite_path<'a, F: CoordFloat, T: Extent<F> + 'a>(
+    members: impl IntoIterator<Item = &'a T>,
+    location: impl Fn(usize) -> Location,
+) -> Option<Vec<Location>> {
+    members.into_iter().enumerate().find_map(|(index, member)| {
+        let mut path = member.non_finite_path()?;
+        path.insert(0, location(index));
+        Some(path)
+    })
+}
+
+fn members_magnitude<'a, F: CoordFloat, T: Extent<F> + 'a>(
+    members: impl IntoIterator<Item = &'a T>,
+) -> F {
+    members.into_iter().fold(F::zero(), |magnitude, member| {
+        magnitude.max(member.magnitude())
+    })
+}
+
+fn members_finite_magnitude<'a, F: CoordFloat, T: Extent<F> + 'a>(
+    members: impl IntoIterator<Item = &'a T>,
+) -> F {
+    members.into_iter().fold(F::zero(), |magnitude, member| {
+        magnitude.max(member.finite_magnitude())
+    })
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::Point<F> {
+    fn magnitude(&self) -> F {
+        coords_magnitude(&[self.0])
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        coords_non_finite_path(&[self.0])
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::Line<F> {
+    fn magnitude(&self) -> F {
+        coords_magnitude(&[self.start, self.end])
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        coords_non_finite_path(&[self.start, self.end])
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::LineString<F> {
+    fn magnitude(&self) -> F {
+        coords_magnitude(&self.0)
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        coords_non_finite_path(&self.0)
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::Polygon<F> {
+    fn magnitude(&self) -> F {
+        self.exterior()
+            .magnitude()
+            .max(members_magnitude(self.interiors()))
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        let exterior_path = self.exterior().non_finite_path().map(|mut path| {
+            path.insert(0, Location::Exterior);
+            path
+        });
+        exterior_path.or_else(|| members_non_finite_path(self.interiors(), Location::Interior))
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::Triangle<F> {
+    fn magnitude(&self) -> F {
+        coords_magnitude(&self.to_array())
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        coords_non_finite_path(&self.to_array())
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::Rect<F> {
+    fn magnitude(&self) -> F {
+        coords_magnitude(&[self.min(), self.max()])
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        coords_non_finite_path(&[self.min(), self.max()])
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::MultiPoint<F> {
+    fn magnitude(&self) -> F {
+        members_magnitude(&self.0)
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        members_non_finite_path(&self.0, Location::Member)
+    }
+
+    fn finite_magnitude(&self) -> F {
+        members_finite_magnitude(&self.0)
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::MultiLineString<F> {
+    fn magnitude(&self) -> F {
+        members_magnitude(&self.0)
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        members_non_finite_path(&self.0, Location::Member)
+    }
+
+    fn finite_magnitude(&self) -> F {
+        members_finite_magnitude(&self.0)
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::MultiPolygon<F> {
+    fn magnitude(&self) -> F {
+        members_magnitude(&self.0)
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        members_non_finite_path(&self.0, Location::Member)
+    }
+
+    fn finite_magnitude(&self) -> F {
+        members_finite_magnitude(&self.0)
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::GeometryCollection<F> {
+    fn magnitude(&self) -> F {
+        members_magnitude(&self.0)
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        members_non_finite_path(&self.0, Location::Member)
+    }
+
+    fn finite_magnitude(&self) -> F {
+        members_finite_magnitude(&self.0)
+    }
+}
+
+impl<F: CoordFloat> Extent<F> for geo_types::Geometry<F> {
+    fn magnitude(&self) -> F {
+        inner_geometry(self).magnitude()
+    }
+
+    fn non_finite_path(&self) -> Option<Vec<Location>> {
+        inner_geometry(self).non_finite_path()
+    }
+
+    fn finite_magnitude(&self) -> F {
+        inner_geometry(self).finite_magnitude()
+    }
+}
+
+fn inner_geometry<F: CoordFloat>(geometry: &geo_types::Geometry<F>) -> &dyn Extent<F> {
+    match geometry {
+        geo_types::Geometry::Point(point) => point,
+        geo_types::Geometry::Line(line) => line,
+        geo_types::Geometry::LineString(line_string) => line_string,
+        geo_types::Geometry::Polygon(polygon) => polygon,
+        geo_types::Geometry::MultiPoint(multi_point) => multi_point,
+        geo_types::Geometry::MultiLineString(multi_line_string) => multi_line_string,
+        geo_types::Geometry::MultiPolygon(multi_polygon) => multi_polygon,
+        geo_types::Geometry::GeometryCollection(geometry_collection) => geometry_collection,
+        geo_types::Geometry::Rect(rect) => rect,
+        geo_types::Geometry::Triangle(triangle) => triangle,
+    }
+}
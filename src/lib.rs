@@ -25,11 +25,33 @@
 //! [`Offset`]: offset/trait.Offset.html
 //! [`offset`]: offset/trait.Offset.html#method.offset
 
+mod backend;
+pub use backend::*;
+
+mod cluster;
+
 mod edge;
 pub use edge::*;
 
+mod extent;
+
+#[cfg(feature = "clipper")]
+mod native;
+
 mod offset;
 pub use offset::*;
 
+mod offset_curve;
+pub use offset_curve::*;
+
+mod options;
+pub use options::*;
+
+mod sanitize;
+pub use sanitize::*;
+
+mod validate;
+pub use validate::*;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,93 @@
+//! Offsets computed by the polygon offsetting of the Clipper library.
+
+use super::*;
+use crate::extent::Extent;
+use geo_clipper::{Clipper, ClipperOpen, EndType, JoinType};
+use geo_types::CoordFloat;
+
+/// Offsets `polygons` by `distance`, shrinking them for negative distances.
+pub(crate) fn offset_polygons<F: CoordFloat>(
+    polygons: &(impl Clipper<F> + Extent<F>),
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+    check_clipper_range(
+        polygons.magnitude() + options.reach(distance),
+        options.factor(),
+    )?;
+    Ok(Clipper::offset(
+        polygons,
+        distance,
+        join_type(distance, options)?,
+        EndType::ClosedPolygon,
+        options.factor(),
+    ))
+}
+
+/// Dilates `line_string` by `distance`, buffering closed line strings as rings.
+pub(crate) fn offset_line_string<F: CoordFloat>(
+    line_string: &geo_types::LineString<F>,
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+    let end_type = if line_string.is_closed() {
+        EndType::ClosedLine
+    } else {
+        match options.cap_style() {
+            CapStyle::Round => EndType::OpenRound(arc_tolerance(distance, options)?),
+            CapStyle::Flat => EndType::OpenButt,
+            CapStyle::Square => EndType::OpenSquare,
+        }
+    };
+    check_clipper_range(
+        line_string.magnitude() + options.reach(distance),
+        options.factor(),
+    )?;
+    Ok(ClipperOpen::offset(
+        &geo_types::MultiLineString(vec![line_string.clone()]),
+        distance,
+        join_type(distance, options)?,
+        end_type,
+        options.factor(),
+    ))
+}
+
+fn join_type<F: CoordFloat>(
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<JoinType, OffsetError> {
+    Ok(match options.join_style() {
+        JoinStyle::Round => JoinType::Round(arc_tolerance(distance, options)?),
+        JoinStyle::Miter { limit } => JoinType::Miter(limit.to_f64().unwrap_or(0.0)),
+        JoinStyle::Bevel => {
+            return Err(OffsetError::BackendFailure(
+                "Clipper does not support bevel joins".to_string(),
+            ))
+        }
+    })
+}
+
+/// Returns the largest distance between arcs and their segments, in scaled units.
+///
+/// Segment counts are applied to full circles since Clipper spreads segments evenly around them.
+fn arc_tolerance<F: CoordFloat>(
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<f64, OffsetError> {
+    options.arc_resolution().validate()?;
+    let radius = distance.abs();
+    let segment_angle = match options.arc_resolution() {
+        ArcResolution::SegmentCount(segment_count) => {
+            F::from(std::f64::consts::TAU).unwrap() / F::from(segment_count.max(1)).unwrap()
+        }
+        ArcResolution::SegmentLength(segment_length) => segment_length / radius,
+        ArcResolution::AngleStep(angle_step) => angle_step,
+        ArcResolution::MaxDeviation(tolerance) => {
+            return Ok((tolerance * options.factor()).to_f64().unwrap_or(0.0));
+        }
+    };
+    // Segments spanning more than half a circle deviate from it by the radius at most.
+    let segment_angle = segment_angle.min(F::from(std::f64::consts::PI).unwrap());
+    let sagitta = radius * (F::one() - (segment_angle / (F::one() + F::one())).cos());
+    Ok((sagitta * options.factor()).to_f64().unwrap_or(0.0))
+}
@@ -1,12 +1,188 @@
 use super::*;
-use geo_clipper::Clipper;
+use crate::extent::coords_magnitude;
+use crate::offset_curve::{create_raw_curve, raw_curve_rings, raw_curve_vertices, RawSegment};
+use crate::validate::overlapping_polygons;
 use geo_types::CoordFloat;
+use itertools::Itertools;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 /// If offset computing fails this error is returned.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum OffsetError {
     /// This error can be produced when manipulating edges.
     EdgeError(EdgeError),
+    /// The offset distance is not a finite number.
+    InvalidDistance,
+    /// A coordinate of the geometry is not a finite number.
+    NonFiniteCoordinate,
+    /// The arc resolution is [`ArcResolution::SegmentCount`] with no segments.
+    ZeroSegmentCount,
+    /// The arc resolution requires infinitely many segments, as does a segment length,
+    /// a deviation or an angle step of zero, or more than 2^20 segments for an arc, and the
+    /// segment count is not bounded.
+    InfiniteSegmentCount,
+    /// The segment length, deviation or angle step of the arc resolution is negative or not a number.
+    InvalidArcResolution,
+    /// The backend could not compute a boolean operation, for the given reason.
+    BackendFailure(String),
+    /// The polygon breaks a rule of validity, see [`OffsetOptions::with_validated_input`].
+    InvalidGeometry(InvalidReason),
+    /// Another error occurred at a given part of the geometry.
+    Located {
+        /// Parts leading to where the error occurred, from the outermost one.
+        path: Vec<Location>,
+        /// The error that occurred there.
+        error: Box<OffsetError>,
+    },
+}
+
+/// Part of a geometry, relative to the geometry containing it.
+///
+/// ```
+/// # use geo_offset::{Location, Offset, OffsetError};
+/// # use geo_types::{polygon, MultiPolygon};
+/// let square = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.)];
+/// let broken = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: f64::NAN, y: 1.)];
+/// let error = MultiPolygon(vec![square, broken]).offset(1.0).unwrap_err();
+/// assert_eq!(error.cause(), &OffsetError::NonFiniteCoordinate);
+/// assert_eq!(error.path(), [Location::Member(1), Location::Exterior, Location::Vertex(2)]);
+/// assert_eq!(
+///     error.to_string(),
+///     "the geometry has a coordinate that is not finite at member 1, exterior ring, vertex 2"
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Location {
+    /// Member at the given index of a multi-geometry or a geometry collection.
+    Member(usize),
+    /// Exterior ring of a polygon.
+    Exterior,
+    /// Interior ring at the given index of a polygon.
+    Interior(usize),
+    /// Edge at the given index of a line or a ring, starting from the vertex with the same index.
+    Edge(usize),
+    /// Vertex at the given index of a line or a ring.
+    Vertex(usize),
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Member(index) => write!(f, "member {}", index),
+            Self::Exterior => write!(f, "exterior ring"),
+            Self::Interior(index) => write!(f, "interior ring {}", index),
+            Self::Edge(index) => write!(f, "edge {}", index),
+            Self::Vertex(index) => write!(f, "vertex {}", index),
+        }
+    }
+}
+
+impl OffsetError {
+    /// Returns the parts leading to where the error occurred, or nothing if the location is unknown.
+    pub fn path(&self) -> &[Location] {
+        match self {
+            Self::Located { path, .. } => path,
+            _ => &[],
+        }
+    }
+
+    /// Returns the error regardless of where it occurred.
+    pub fn cause(&self) -> &OffsetError {
+        match self {
+            Self::Located { error, .. } => error,
+            _ => self,
+        }
+    }
+
+    /// Locates the error within the part at `location`.
+    pub(crate) fn at(self, location: Location) -> Self {
+        self.within(vec![location])
+    }
+
+    /// Locates the error within the parts along `path`.
+    pub(crate) fn within(self, mut path: Vec<Location>) -> Self {
+        if path.is_empty() {
+            return self;
+        }
+        match self {
+            Self::Located {
+                path: inner_path,
+                error,
+            } => {
+                path.extend(inner_path);
+                Self::Located { path, error }
+            }
+            error => Self::Located {
+                path,
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// Replaces the index of the edge where the error occurred.
+    pub(crate) fn map_edge(self, index: impl Fn(usize) -> usize) -> Self {
+        match self {
+            Self::Located { path, error } => Self::Located {
+                path: path
+                    .into_iter()
+                    .map(|location| match location {
+                        Location::Edge(edge) => Location::Edge(index(edge)),
+                        location => location,
+                    })
+                    .collect(),
+                error,
+            },
+            error => error,
+        }
+    }
+}
+
+impl From<EdgeError> for OffsetError {
+    fn from(error: EdgeError) -> Self {
+        Self::EdgeError(error)
+    }
+}
+
+impl std::fmt::Display for OffsetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EdgeError(error) => write!(f, "invalid edge: {}", error),
+            Self::InvalidDistance => write!(f, "the offset distance is not finite"),
+            Self::NonFiniteCoordinate => {
+                write!(f, "the geometry has a coordinate that is not finite")
+            }
+            Self::ZeroSegmentCount => write!(f, "the arc resolution has no segments"),
+            Self::InfiniteSegmentCount => {
+                write!(f, "the arc resolution requires infinitely many segments")
+            }
+            Self::InvalidArcResolution => {
+                write!(f, "the arc resolution is negative or not a number")
+            }
+            Self::BackendFailure(reason) => write!(f, "the boolean operation failed: {}", reason),
+            Self::InvalidGeometry(reason) => write!(f, "the geometry is invalid: {}", reason),
+            Self::Located { path, error } => {
+                write!(f, "{} at ", error)?;
+                for (index, location) in path.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", location)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OffsetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::EdgeError(error) => Some(error),
+            Self::Located { error, .. } => Some(error),
+            _ => None,
+        }
+    }
 }
 
 /// Resolution of arcs generated around corners for positive offsets.
@@ -23,6 +199,31 @@ pub enum ArcResolution<F: CoordFloat> {
     SegmentCount(usize),
     /// Sets the desired segment length, so that the number of segments is chosen based on the length of the arc.
     SegmentLength(F),
+    /// Sets the largest distance allowed between an arc and its segments, so that the number of
+    /// segments is chosen based on the radius and the angle of the arc.
+    ///
+    /// ```
+    /// # use geo_offset::{ArcResolution, Offset, OffsetOptions};
+    /// let options = OffsetOptions::new().with_arc_resolution(ArcResolution::MaxDeviation(0.01));
+    /// let circle = geo_types::Point::new(0.0, 0.0).offset_with_options(1.0, &options)?;
+    /// // A segment spanning an angle θ deviates from the circle by 1 - cos(θ / 2).
+    /// assert_eq!(circle.0[0].exterior().lines().count(), 23);
+    /// # Ok::<(), geo_offset::OffsetError>(())
+    /// ```
+    MaxDeviation(F),
+    /// Sets the angle in radians spanned by each segment, so that the number of segments is
+    /// proportional to the angle of the arc.
+    ///
+    /// ```
+    /// # use geo_offset::{ArcResolution, Offset, OffsetOptions};
+    /// // Eight segments per quadrant.
+    /// let angle_step = std::f64::consts::FRAC_PI_2 / 8.0;
+    /// let options = OffsetOptions::new().with_arc_resolution(ArcResolution::AngleStep(angle_step));
+    /// let circle = geo_types::Point::new(0.0, 0.0).offset_with_options(1.0, &options)?;
+    /// assert_eq!(circle.0[0].exterior().lines().count(), 32);
+    /// # Ok::<(), geo_offset::OffsetError>(())
+    /// ```
+    AngleStep(F),
 }
 
 impl<F: CoordFloat> Default for ArcResolution<F> {
@@ -31,238 +232,649 @@ impl<F: CoordFloat> Default for ArcResolution<F> {
     }
 }
 
+impl<F: CoordFloat> ArcResolution<F> {
+    /// Returns an error if the resolution cannot yield a finite, positive number of segments.
+    pub(crate) fn validate(&self) -> Result<(), OffsetError> {
+        let parameter = match *self {
+            Self::SegmentCount(0) => return Err(OffsetError::ZeroSegmentCount),
+            Self::SegmentCount(_) => return Ok(()),
+            Self::SegmentLength(parameter)
+            | Self::MaxDeviation(parameter)
+            | Self::AngleStep(parameter) => parameter,
+        };
+        if parameter > F::zero() {
+            Ok(())
+        } else if parameter.is_zero() {
+            Err(OffsetError::InfiniteSegmentCount)
+        } else {
+            Err(OffsetError::InvalidArcResolution)
+        }
+    }
+
+    /// Returns the number of segments approximating an arc spanning `angle` radians.
+    ///
+    /// The count is not rounded to an integer when it depends on the size of the arc.
+    pub(crate) fn segment_count(&self, radius: F, angle: F) -> Result<F, OffsetError> {
+        self.validate()?;
+        Ok(match *self {
+            Self::SegmentCount(segment_count) => F::from(segment_count).unwrap(),
+            Self::SegmentLength(segment_length) => {
+                let arc_length = angle * radius;
+                (arc_length / segment_length).floor()
+            }
+            Self::MaxDeviation(tolerance) => {
+                // The sagitta of a segment spanning θ radians is radius * (1 - cos(θ / 2)).
+                let half_segment_angle = (F::one() - tolerance / radius).max(-F::one()).acos();
+                (angle / (half_segment_angle + half_segment_angle)).ceil()
+            }
+            Self::AngleStep(angle_step) => {
+                // Angles recovered from coordinates are slightly off, which must not add a segment
+                // to arcs spanning an exact multiple of the step.
+                let step_count = angle / angle_step * (F::one() - F::epsilon().sqrt());
+                step_count.ceil()
+            }
+        })
+    }
+}
+
+/// Placement of the segments approximating arcs relative to the true arcs.
+///
+/// ```
+/// # use geo_offset::ArcApproximation;
+/// // Arc vertices lie on the true arcs by default.
+/// let arc_approximation: ArcApproximation = Default::default();
+/// assert_eq!(arc_approximation, ArcApproximation::Inscribed);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ArcApproximation {
+    /// Vertices lie on the true arcs, so that segments cut inside them by up to their sagitta.
+    #[default]
+    Inscribed,
+    /// Segments are tangent to the true arcs, so that buffers cover every point within the
+    /// offset distance.
+    ///
+    /// Segments span at most a third of a circle, to keep vertices within twice the radius.
+    Circumscribed,
+}
+
+/// Shape of the corners generated on the outer side of vertices for positive offsets.
+///
+/// ```
+/// # use geo_offset::JoinStyle;
+/// // Corners are rounded by default.
+/// let join_style: JoinStyle<f32> = Default::default();
+/// assert_eq!(join_style, JoinStyle::Round);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum JoinStyle<F: CoordFloat> {
+    /// Corners are rounded with arcs following the [`ArcResolution`].
+    #[default]
+    Round,
+    /// Corners are kept sharp by extending the offset edges until they meet.
+    ///
+    /// Corners whose miter length exceeds `limit` times the offset distance are beveled instead.
+    Miter { limit: F },
+    /// Corners are cut by joining the ends of the offset edges.
+    Bevel,
+}
+
+/// Shape of the ends of buffered lines.
+///
+/// ```
+/// # use geo_offset::CapStyle;
+/// // Ends are rounded by default.
+/// let cap_style: CapStyle = Default::default();
+/// assert_eq!(cap_style, CapStyle::Round);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum CapStyle {
+    /// Ends are rounded with half circles following the [`ArcResolution`].
+    #[default]
+    Round,
+    /// Ends stop exactly at the end points.
+    Flat,
+    /// Ends are extended by the offset distance and squared off.
+    Square,
+}
+
+/// Offset of a geometry without the members which could not be offset, with their errors.
+///
+/// See [`OffsetOptions::with_lenient`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagnosedOffset<F: CoordFloat> {
+    /// Offset of the members which could be offset.
+    pub offset: geo_types::MultiPolygon<F>,
+    /// Errors of the members left out, located within the geometry.
+    pub diagnostics: Vec<OffsetError>,
+    /// Repairs made to the geometry beforehand, see [`OffsetOptions::with_sanitized_input`].
+    pub repairs: Vec<Repair>,
+}
+
+impl<F: CoordFloat> Default for DiagnosedOffset<F> {
+    fn default() -> Self {
+        Self {
+            offset: geo_types::MultiPolygon(Vec::new()),
+            diagnostics: Vec::new(),
+            repairs: Vec::new(),
+        }
+    }
+}
+
 pub trait Offset<F: CoordFloat> {
     fn offset(&self, distance: F) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
-        self.offset_with_arc_resolution(distance, Default::default())
+        self.offset_with_options(distance, &Default::default())
     }
 
     fn offset_with_arc_resolution(
         &self,
         distance: F,
         arc_resolution: ArcResolution<F>,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        self.offset_with_options(
+            distance,
+            &OffsetOptions::new().with_arc_resolution(arc_resolution),
+        )
+    }
+
+    /// Computes the offset using the given [`OffsetAlgorithm`].
+    fn offset_with_algorithm(
+        &self,
+        distance: F,
+        algorithm: OffsetAlgorithm,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        self.offset_with_options(distance, &OffsetOptions::new().with_algorithm(algorithm))
+    }
+
+    /// Computes the offset using the given [`OffsetOptions`].
+    fn offset_with_options(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError>;
+
+    /// Computes the offset using the given [`OffsetOptions`], along with the errors of the members left out in
+    /// lenient mode.
+    fn offset_with_diagnostics(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<DiagnosedOffset<F>, OffsetError> {
+        Ok(DiagnosedOffset {
+            offset: self.offset_with_options(distance, options)?,
+            ..Default::default()
+        })
+    }
 }
 
-impl<F: CoordFloat> Offset<F> for geo_types::GeometryCollection<F> {
-    fn offset_with_arc_resolution(
+impl<F: CoordFloat + MaybeSendSync> Offset<F> for geo_types::GeometryCollection<F> {
+    fn offset_with_options(
         &self,
         distance: F,
-        arc_resolution: ArcResolution<F>,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
-        let mut geometry_collection_with_offset = geo_types::MultiPolygon::<F>(Vec::new());
-        for geometry in self.0.iter() {
-            let geometry_with_offset = geometry.offset_with_arc_resolution(distance, arc_resolution)?;
-            geometry_collection_with_offset = geometry_collection_with_offset
-                .union(&geometry_with_offset, F::from(1000.0).unwrap());
+        Ok(self.offset_with_diagnostics(distance, options)?.offset)
+    }
+
+    fn offset_with_diagnostics(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<DiagnosedOffset<F>, OffsetError> {
+        if options.sanitized_input() {
+            return offset_sanitized(self, distance, options);
         }
-        Ok(geometry_collection_with_offset)
+        let options = &options.resolve(self, distance)?;
+        let (geometries_with_offset, diagnostics) = offset_members(&self.0, distance, options)?;
+        Ok(DiagnosedOffset {
+            offset: options.union_all(geometries_with_offset)?,
+            diagnostics,
+            ..Default::default()
+        })
     }
 }
 
-impl<F: CoordFloat> Offset<F> for geo_types::Geometry<F> {
-    fn offset_with_arc_resolution(
+impl<F: CoordFloat + MaybeSendSync> Offset<F> for geo_types::Geometry<F> {
+    fn offset_with_options(
         &self,
         distance: F,
-        arc_resolution: ArcResolution<F>,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        Ok(self.offset_with_diagnostics(distance, options)?.offset)
+    }
+
+    fn offset_with_diagnostics(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<DiagnosedOffset<F>, OffsetError> {
+        if options.sanitized_input() {
+            return offset_sanitized(self, distance, options);
+        }
+        let options = &options.resolve(self, distance)?;
         match self {
-            geo_types::Geometry::Point(point) => {
-                point.offset_with_arc_resolution(distance, arc_resolution)
-            }
-            geo_types::Geometry::Line(line) => {
-                line.offset_with_arc_resolution(distance, arc_resolution)
-            }
+            geo_types::Geometry::Point(point) => point.offset_with_diagnostics(distance, options),
+            geo_types::Geometry::Line(line) => line.offset_with_diagnostics(distance, options),
             geo_types::Geometry::LineString(line_tring) => {
-                line_tring.offset_with_arc_resolution(distance, arc_resolution)
+                line_tring.offset_with_diagnostics(distance, options)
             }
             geo_types::Geometry::Triangle(triangle) => triangle
                 .to_polygon()
-                .offset_with_arc_resolution(distance, arc_resolution),
-            geo_types::Geometry::Rect(rect) => rect
-                .to_polygon()
-                .offset_with_arc_resolution(distance, arc_resolution),
+                .offset_with_diagnostics(distance, options),
+            geo_types::Geometry::Rect(rect) => {
+                rect.to_polygon().offset_with_diagnostics(distance, options)
+            }
             geo_types::Geometry::Polygon(polygon) => {
-                polygon.offset_with_arc_resolution(distance, arc_resolution)
+                polygon.offset_with_diagnostics(distance, options)
             }
             geo_types::Geometry::MultiPoint(multi_point) => {
-                multi_point.offset_with_arc_resolution(distance, arc_resolution)
+                multi_point.offset_with_diagnostics(distance, options)
             }
             geo_types::Geometry::MultiLineString(multi_line_string) => {
-                multi_line_string.offset_with_arc_resolution(distance, arc_resolution)
+                multi_line_string.offset_with_diagnostics(distance, options)
             }
             geo_types::Geometry::MultiPolygon(multi_polygon) => {
-                multi_polygon.offset_with_arc_resolution(distance, arc_resolution)
+                multi_polygon.offset_with_diagnostics(distance, options)
             }
             geo_types::Geometry::GeometryCollection(geometry_collection) => {
-                geometry_collection.offset_with_arc_resolution(distance, arc_resolution)
+                geometry_collection.offset_with_diagnostics(distance, options)
             }
         }
     }
 }
 
-impl<F: CoordFloat> Offset<F> for geo_types::MultiPolygon<F> {
-    fn offset_with_arc_resolution(
+impl<F: CoordFloat + MaybeSendSync> Offset<F> for geo_types::MultiPolygon<F> {
+    fn offset_with_options(
         &self,
         distance: F,
-        arc_resolution: ArcResolution<F>,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
-        let mut polygons = geo_types::MultiPolygon::<F>(Vec::new());
-        for polygon in self.0.iter() {
-            let polygon_with_offset = polygon.offset_with_arc_resolution(distance, arc_resolution)?;
-            polygons = polygons.union(&polygon_with_offset, F::from(1000.0).unwrap());
+        Ok(self.offset_with_diagnostics(distance, options)?.offset)
+    }
+
+    fn offset_with_diagnostics(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<DiagnosedOffset<F>, OffsetError> {
+        if options.sanitized_input() {
+            return offset_sanitized(self, distance, options);
+        }
+        let mut options = options.resolve(self, distance)?;
+        let mut overlaps = Vec::new();
+        if options.validated_input() {
+            // In lenient mode polygons are checked on their own, so that invalid ones can be left out.
+            if options.lenient() {
+                overlaps = overlapping_polygons(&self.0);
+            } else if let Some(invalidity) =
+                self.validate().err().unwrap_or_default().into_iter().next()
+            {
+                return Err(invalidity.into());
+            } else {
+                options = options.with_validated_input(false);
+            }
+        }
+        let options = &options;
+        // Polygons are offset one by one in lenient mode, so that failures can be told apart.
+        #[cfg(feature = "clipper")]
+        if options.algorithm() == OffsetAlgorithm::Clipper && !options.lenient() {
+            return Ok(DiagnosedOffset {
+                offset: native::offset_polygons(self, distance, options)?,
+                ..Default::default()
+            });
+        }
+
+        // Polygons overlapping an earlier one are emptied so that the other members keep their index.
+        let mut polygons = std::borrow::Cow::Borrowed(&self.0);
+        for overlap in &overlaps {
+            if let [Location::Member(index)] = overlap.path[..] {
+                polygons.to_mut()[index] =
+                    geo_types::Polygon::new(geo_types::LineString(Vec::new()), Vec::new());
+            }
+        }
+        let (polygons_with_offset, diagnostics) = offset_members(&polygons, distance, options)?;
+        Ok(DiagnosedOffset {
+            offset: options.union_all(polygons_with_offset)?,
+            diagnostics: overlaps
+                .into_iter()
+                .map(OffsetError::from)
+                .chain(diagnostics)
+                .collect(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Bound on values shared between threads, which only applies if the `rayon` feature is enabled.
+#[cfg(feature = "rayon")]
+pub(crate) trait MaybeSendSync: Send + Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Send + Sync> MaybeSendSync for T {}
+
+/// Bound on values shared between threads, which only applies if the `rayon` feature is enabled.
+#[cfg(not(feature = "rayon"))]
+pub(crate) trait MaybeSendSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSendSync for T {}
+
+/// Offsets every member of a multi-geometry, in parallel if the `rayon` feature is enabled.
+///
+/// Members which fail are left out in lenient mode, and their errors are gathered with those of nested members.
+fn offset_members<F, T>(
+    members: &[T],
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<(Vec<geo_types::MultiPolygon<F>>, Vec<OffsetError>), OffsetError>
+where
+    F: CoordFloat + MaybeSendSync,
+    T: Offset<F> + MaybeSendSync,
+{
+    #[cfg(feature = "rayon")]
+    let members = members.par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let members = members.iter();
+    let members_with_offset: Vec<_> = members
+        .map(|member| member.offset_with_diagnostics(distance, options))
+        .collect();
+
+    let mut parts = Vec::new();
+    let mut diagnostics = Vec::new();
+    for (index, member_with_offset) in members_with_offset.into_iter().enumerate() {
+        let location = Location::Member(index);
+        match member_with_offset {
+            Ok(member_with_offset) => {
+                parts.push(member_with_offset.offset);
+                diagnostics.extend(
+                    member_with_offset
+                        .diagnostics
+                        .into_iter()
+                        .map(|error| error.at(location)),
+                );
+            }
+            Err(error) if options.lenient() => diagnostics.push(error.at(location)),
+            Err(error) => return Err(error.at(location)),
         }
-        Ok(polygons)
     }
+    Ok((parts, diagnostics))
+}
+
+/// Offsets the repaired copy of `geometry`, reporting the repairs along with the offset.
+fn offset_sanitized<F, G>(
+    geometry: &G,
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<DiagnosedOffset<F>, OffsetError>
+where
+    F: CoordFloat,
+    G: Offset<F> + Sanitize<F>,
+{
+    let sanitized = geometry.sanitize();
+    let options = options.clone().with_sanitized_input(false);
+    let geometry_with_offset = sanitized
+        .geometry
+        .offset_with_diagnostics(distance, &options)?;
+    Ok(DiagnosedOffset {
+        repairs: sanitized.repairs,
+        ..geometry_with_offset
+    })
 }
 
 impl<F: CoordFloat> Offset<F> for geo_types::Polygon<F> {
-    fn offset_with_arc_resolution(
+    fn offset_with_options(
         &self,
         distance: F,
-        arc_resolution: ArcResolution<F>,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
-        let exterior_with_offset = self
-            .exterior()
-            .offset_with_arc_resolution(distance.abs(), arc_resolution)?;
-        let interiors_with_offset = geo_types::MultiLineString::<F>(self.interiors().to_vec())
-            .offset_with_arc_resolution(distance.abs(), arc_resolution)?;
-
-        Ok(if distance.is_sign_positive() {
-            self.union(&exterior_with_offset, F::from(1000.0).unwrap())
-                .union(&interiors_with_offset, F::from(1000.0).unwrap())
+        if options.sanitized_input() {
+            return Ok(offset_sanitized(self, distance, options)?.offset);
+        }
+        let options = &options.resolve(self, distance)?;
+        if options.validated_input() {
+            let invalidities = self.validate().err().unwrap_or_default();
+            if let Some(invalidity) = invalidities.into_iter().next() {
+                return Err(invalidity.into());
+            }
+        }
+        #[cfg(feature = "clipper")]
+        if options.algorithm() == OffsetAlgorithm::Clipper {
+            return native::offset_polygons(self, distance, options);
+        }
+
+        // The polygon lies on the left of its oriented rings, so it grows on their right and shrinks on their left.
+        let sides = if distance.is_sign_negative() {
+            Sides::Left
         } else {
-            self.difference(&exterior_with_offset, F::from(1000.0).unwrap())
-                .difference(&interiors_with_offset, F::from(1000.0).unwrap())
+            Sides::Right
+        };
+        // Boolean operations fill areas with a non-zero winding number, so holes must turn the other way.
+        let rings = std::iter::once((Location::Exterior, self.exterior(), true)).chain(
+            self.interiors()
+                .iter()
+                .enumerate()
+                .map(|(index, interior)| (Location::Interior(index), interior, false)),
+        );
+        let mut oriented_rings = Vec::new();
+        let mut bands = Vec::new();
+        for (location, ring, counter_clockwise) in rings {
+            let reversed = is_counter_clockwise(ring) != counter_clockwise;
+            let ring = oriented(ring.clone(), counter_clockwise);
+            let (coords, edges, edge_indices) = distinct_edges(&ring.0);
+            let outline = if edges.is_empty() {
+                let options = options.clone().with_single_sided(false);
+                ring.offset_with_options(distance.abs(), &options)
+            } else {
+                create_outline(&coords, &edges, true, sides, distance.abs(), options)
+                    .map_err(|error| error.map_edge(|edge| edge_indices[edge]))
+            };
+            // Edges are numbered as in the input ring, which may have been reversed.
+            let last_edge = ring.0.len().saturating_sub(2);
+            bands.extend(outline.map_err(|error| {
+                error
+                    .map_edge(|edge| if reversed { last_edge - edge } else { edge })
+                    .at(location)
+            })?);
+            oriented_rings.push(ring);
+        }
+
+        let mut oriented_rings = oriented_rings.into_iter();
+        let exterior = oriented_rings.next().unwrap();
+        let interiors = oriented_rings.collect();
+        let polygon = geo_types::MultiPolygon(vec![geo_types::Polygon::new(exterior, interiors)]);
+        let bands = geo_types::MultiPolygon(bands);
+        if distance.is_sign_positive() {
+            options.union(&polygon, &bands)
+        } else {
+            options.difference(&polygon, &bands)
+        }
+    }
+
+    fn offset_with_diagnostics(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<DiagnosedOffset<F>, OffsetError> {
+        if options.sanitized_input() {
+            return offset_sanitized(self, distance, options);
+        }
+        Ok(DiagnosedOffset {
+            offset: self.offset_with_options(distance, options)?,
+            ..Default::default()
         })
     }
 }
 
-impl<F: CoordFloat> Offset<F> for geo_types::MultiLineString<F> {
-    fn offset_with_arc_resolution(
+impl<F: CoordFloat + MaybeSendSync> Offset<F> for geo_types::MultiLineString<F> {
+    fn offset_with_options(
         &self,
         distance: F,
-        arc_resolution: ArcResolution<F>,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
-        if distance < F::zero() {
-            return Ok(geo_types::MultiPolygon(Vec::new()));
-        }
+        Ok(self.offset_with_diagnostics(distance, options)?.offset)
+    }
 
-        let mut multi_line_string_with_offset = geo_types::MultiPolygon::<F>(Vec::new());
-        for line_string in self.0.iter() {
-            let line_string_with_offset =
-                line_string.offset_with_arc_resolution(distance, arc_resolution)?;
-            multi_line_string_with_offset = multi_line_string_with_offset
-                .union(&line_string_with_offset, F::from(1000.0).unwrap());
+    fn offset_with_diagnostics(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<DiagnosedOffset<F>, OffsetError> {
+        if options.sanitized_input() {
+            return offset_sanitized(self, distance, options);
         }
-        Ok(multi_line_string_with_offset)
+        let options = &options.resolve(self, distance)?;
+        if distance < F::zero() && !options.single_sided() {
+            return Ok(DiagnosedOffset::default());
+        }
+
+        let (line_strings_with_offset, diagnostics) = offset_members(&self.0, distance, options)?;
+        Ok(DiagnosedOffset {
+            offset: options.union_all(line_strings_with_offset)?,
+            diagnostics,
+            ..Default::default()
+        })
     }
 }
 
 impl<F: CoordFloat> Offset<F> for geo_types::LineString<F> {
-    fn offset_with_arc_resolution(
+    fn offset_with_options(
         &self,
         distance: F,
-        arc_resolution: ArcResolution<F>,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
-        if distance < F::zero() {
+        if options.sanitized_input() {
+            return Ok(offset_sanitized(self, distance, options)?.offset);
+        }
+        let options = &options.resolve(self, distance)?;
+        if distance < F::zero() && !options.single_sided() {
             return Ok(geo_types::MultiPolygon(Vec::new()));
         }
 
-        let mut line_string_with_offset = geo_types::MultiPolygon::<F>(Vec::new());
-        for line in self.lines() {
-            let line_with_offset = line.offset_with_arc_resolution(distance, arc_resolution)?;
-            line_string_with_offset =
-                line_string_with_offset.union(&line_with_offset, F::from(1000.0).unwrap());
+        #[cfg(feature = "clipper")]
+        if options.algorithm() == OffsetAlgorithm::Clipper && !options.single_sided() {
+            return native::offset_line_string(self, distance, options);
         }
 
-        let line_string_with_offset = line_string_with_offset.0.iter().skip(1).fold(
-            geo_types::MultiPolygon::<F>(
-                line_string_with_offset
-                    .0
-                    .get(0)
-                    .map(|polygon| vec![polygon.clone()])
-                    .unwrap_or_default(),
-            ),
-            |result, hole| result.difference(hole, F::from(1000.0).unwrap()),
-        );
+        let (coords, edges, edge_indices) = distinct_edges(&self.0);
+        if edges.is_empty() {
+            return match coords.first() {
+                Some(coord) if !options.single_sided() => {
+                    geo_types::Line::new(*coord, *coord).offset_with_options(distance, options)
+                }
+                _ => Ok(geo_types::MultiPolygon(Vec::new())),
+            };
+        }
+
+        let sides = Sides::new(distance, options);
+        let outline = create_outline(
+            &coords,
+            &edges,
+            self.is_closed(),
+            sides,
+            distance.abs(),
+            options,
+        )
+        .map_err(|error| error.map_edge(|edge| edge_indices[edge]))?;
+        options.union(&outline, &geo_types::MultiPolygon(Vec::new()))
+    }
 
-        Ok(line_string_with_offset)
+    fn offset_with_diagnostics(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<DiagnosedOffset<F>, OffsetError> {
+        if options.sanitized_input() {
+            return offset_sanitized(self, distance, options);
+        }
+        Ok(DiagnosedOffset {
+            offset: self.offset_with_options(distance, options)?,
+            ..Default::default()
+        })
     }
 }
 
 impl<F: CoordFloat> Offset<F> for geo_types::Line<F> {
-    fn offset_with_arc_resolution(
+    fn offset_with_options(
         &self,
         distance: F,
-        arc_resolution: ArcResolution<F>,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        let options = &options.resolve(self, distance)?;
+        if options.single_sided() {
+            return geo_types::LineString(vec![self.start, self.end])
+                .offset_with_options(distance, options);
+        }
+
         if distance < F::zero() {
             return Ok(geo_types::MultiPolygon(Vec::new()));
         }
 
-        let v1 = &self.start;
-        let v2 = &self.end;
-        let e1 = Edge::new(v1, v2);
-
-        if let (Ok(in_normal), Ok(out_normal)) = (e1.inwards_normal(), e1.outwards_normal()) {
-            let offsets = [
-                e1.with_offset(in_normal.x * distance, in_normal.y * distance),
-                e1.inverse_with_offset(out_normal.x * distance, out_normal.y * distance),
-            ];
-
-            let len = 2;
-            let mut vertices = Vec::new();
-
-            for i in 0..len {
-                let current_edge = offsets.get(i).unwrap();
-                let prev_edge = offsets.get((i + len + 1) % len).unwrap();
-                create_arc(
-                    &mut vertices,
-                    if i == 0 { v1 } else { v2 },
-                    distance,
-                    &prev_edge.next,
-                    &current_edge.current,
-                    arc_resolution,
-                    true,
-                );
-            }
+        let edge = Edge::new(&self.start, &self.end);
+
+        if edge.inwards_normal().is_ok() {
+            let mut vertices = create_cap_vertices(&edge.inverse(), distance, options)?;
+            vertices.extend(create_cap_vertices(&edge, distance, options)?);
 
             Ok(geo_types::MultiPolygon(vec![geo_types::Polygon::new(
                 geo_types::LineString(vertices),
                 vec![],
             )]))
         } else {
-            geo_types::Point::from(self.start).offset_with_arc_resolution(distance, arc_resolution)
+            match options.cap_style() {
+                CapStyle::Round => {
+                    geo_types::Point::from(self.start).offset_with_options(distance, options)
+                }
+                CapStyle::Flat => Ok(geo_types::MultiPolygon(Vec::new())),
+                CapStyle::Square => {
+                    let corner = geo_types::Coord::from((distance, distance));
+                    Ok(geo_types::MultiPolygon(vec![geo_types::Rect::new(
+                        self.start - corner,
+                        self.start + corner,
+                    )
+                    .to_polygon()]))
+                }
+            }
         }
     }
 }
 
-impl<F: CoordFloat> Offset<F> for geo_types::MultiPoint<F> {
-    fn offset_with_arc_resolution(
+impl<F: CoordFloat + MaybeSendSync> Offset<F> for geo_types::MultiPoint<F> {
+    fn offset_with_options(
         &self,
         distance: F,
-        arc_resolution: ArcResolution<F>,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        Ok(self.offset_with_diagnostics(distance, options)?.offset)
+    }
+
+    fn offset_with_diagnostics(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<DiagnosedOffset<F>, OffsetError> {
+        if options.sanitized_input() {
+            return offset_sanitized(self, distance, options);
+        }
+        let options = &options.resolve(self, distance)?;
         if distance < F::zero() {
-            return Ok(geo_types::MultiPolygon(Vec::new()));
+            return Ok(DiagnosedOffset::default());
         }
 
-        let mut multi_point_with_offset = geo_types::MultiPolygon::<F>(Vec::new());
-        for point in self.0.iter() {
-            let point_with_offset = point.offset_with_arc_resolution(distance, arc_resolution)?;
-            multi_point_with_offset =
-                multi_point_with_offset.union(&point_with_offset, F::from(1000.0).unwrap());
-        }
-        Ok(multi_point_with_offset)
+        let (points_with_offset, diagnostics) = offset_members(&self.0, distance, options)?;
+        Ok(DiagnosedOffset {
+            offset: options.union_all(points_with_offset)?,
+            diagnostics,
+            ..Default::default()
+        })
     }
 }
 
 impl<F: CoordFloat> Offset<F> for geo_types::Point<F> {
-    fn offset_with_arc_resolution(
+    fn offset_with_options(
         &self,
         distance: F,
-        arc_resolution: ArcResolution<F>,
+        options: &OffsetOptions<F>,
     ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        let options = &options.resolve(self, distance)?;
         if distance < F::zero() {
             return Ok(geo_types::MultiPolygon(Vec::new()));
         }
@@ -270,26 +882,45 @@ impl<F: CoordFloat> Offset<F> for geo_types::Point<F> {
         let tau = F::from(std::f64::consts::TAU).unwrap();
         let mut angle = F::zero();
 
-        let segment_count = match arc_resolution {
-            ArcResolution::SegmentCount(segment_count) => segment_count,
-            ArcResolution::SegmentLength(segment_length) => {
-                let circumference = tau * distance;
-                (circumference / segment_length).to_usize().unwrap()
-            },
-        };
+        let segment_count = options.arc_segment_count(distance, tau)?;
         let segment_count = segment_count.max(3); // A circle should have at least three sides :)
+        let radius = match options.arc_approximation() {
+            ArcApproximation::Inscribed => distance,
+            ArcApproximation::Circumscribed => {
+                let half_segment_angle =
+                    F::from(std::f64::consts::PI / segment_count as f64).unwrap();
+                distance / half_segment_angle.cos()
+            }
+        };
 
-        let contour = (0..segment_count)
-            .map(|_| {
-                angle =
-                    angle + F::from(2.0 * std::f64::consts::PI / segment_count as f64).unwrap(); // counter-clockwise
+        let contour = if options.anchored_arcs() {
+            let segment_angle = tau / F::from(segment_count).unwrap();
+            let first_angle = match options.arc_approximation() {
+                ArcApproximation::Inscribed => F::zero(),
+                ArcApproximation::Circumscribed => segment_angle / (F::one() + F::one()),
+            };
+            (0..segment_count)
+                .map(|i| {
+                    let angle = first_angle + segment_angle * F::from(i).unwrap();
+                    geo_types::Coord::from((
+                        self.x() + (radius * angle.cos()),
+                        self.y() + (radius * angle.sin()),
+                    ))
+                })
+                .collect()
+        } else {
+            (0..segment_count)
+                .map(|_| {
+                    angle =
+                        angle + F::from(2.0 * std::f64::consts::PI / segment_count as f64).unwrap(); // counter-clockwise
 
-                geo_types::Coord::from((
-                    self.x() + (distance * angle.cos()),
-                    self.y() + (distance * angle.sin()),
-                ))
-            })
-            .collect();
+                    geo_types::Coord::from((
+                        self.x() + (radius * angle.cos()),
+                        self.y() + (radius * angle.sin()),
+                    ))
+                })
+                .collect()
+        };
 
         Ok(geo_types::MultiPolygon(vec![geo_types::Polygon::new(
             contour,
@@ -298,15 +929,230 @@ impl<F: CoordFloat> Offset<F> for geo_types::Point<F> {
     }
 }
 
-fn create_arc<F: CoordFloat>(
+/// Sides of lines covered by their buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Sides {
+    Both,
+    Left,
+    Right,
+}
+
+impl Sides {
+    fn new<F: CoordFloat>(distance: F, options: &OffsetOptions<F>) -> Self {
+        if !options.single_sided() {
+            Self::Both
+        } else if distance.is_sign_negative() {
+            Self::Right
+        } else {
+            Self::Left
+        }
+    }
+
+    fn covers_left(self) -> bool {
+        self != Self::Right
+    }
+
+    fn covers_right(self) -> bool {
+        self != Self::Left
+    }
+}
+
+/// Creates the outline of the area lying within `distance` of a line on the given sides, turning counter-clockwise.
+///
+/// The outline goes forward along the right side and back along the left side. Closed lines have one ring per side.
+/// Overlaps where the outline crosses itself must be resolved by a union.
+fn create_outline<F: CoordFloat>(
+    coords: &[geo_types::Coord<F>],
+    edges: &[Edge<F>],
+    closed: bool,
+    sides: Sides,
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+    let single_sided = sides != Sides::Both;
+    let reversed_edges: Vec<_> = edges.iter().rev().map(Edge::inverse).collect();
+    let right = if sides.covers_right() {
+        Some(create_raw_curve(edges, closed, -distance, options)?)
+    } else {
+        None
+    };
+    let left = if sides.covers_left() {
+        let segments = create_raw_curve(&reversed_edges, closed, -distance, options)
+            .map_err(|error| error.map_edge(|edge| edges.len() - 1 - edge))?;
+        Some(segments)
+    } else {
+        None
+    };
+
+    let rings = if closed {
+        // Where a side collapses, its raw curve turns the wrong way around areas it should cover.
+        let magnitude = coords_magnitude(coords);
+        let side_rings = |segments: Option<Vec<RawSegment<F>>>, edges: &[Edge<F>], ring| {
+            segments.map_or_else(
+                || vec![ring],
+                |segments| raw_curve_rings(edges, &segments, single_sided, distance, magnitude),
+            )
+        };
+        let mut rings = side_rings(right, edges, coords.to_vec());
+        rings.extend(side_rings(
+            left,
+            &reversed_edges,
+            coords.iter().rev().copied().collect(),
+        ));
+        rings
+    } else {
+        let side_vertices = |segments: Option<Vec<RawSegment<F>>>, line| {
+            segments.map_or(line, |segments| {
+                raw_curve_vertices(&segments, false, single_sided)
+            })
+        };
+        let right = side_vertices(right, coords.to_vec());
+        let left = side_vertices(left, coords.iter().rev().copied().collect());
+        match (edges.first(), edges.last()) {
+            (Some(first_edge), Some(last_edge)) if sides == Sides::Both => {
+                let mut ring = right;
+                ring.extend(
+                    create_cap_vertices(last_edge, distance, options)
+                        .map_err(|error| error.at(Location::Edge(edges.len() - 1)))?,
+                );
+                ring.extend(left);
+                ring.extend(
+                    create_cap_vertices(&first_edge.inverse(), distance, options)
+                        .map_err(|error| error.at(Location::Edge(0)))?,
+                );
+                vec![ring]
+            }
+            _ => {
+                let mut ring = right;
+                ring.extend(left);
+                vec![ring]
+            }
+        }
+    };
+
+    Ok(geo_types::MultiPolygon(
+        rings
+            .into_iter()
+            .map(|mut ring| {
+                ring.dedup();
+                geo_types::Polygon::new(geo_types::LineString(ring), Vec::new())
+            })
+            .collect(),
+    ))
+}
+
+/// Returns the vertices going counter-clockwise around the end of `edge`, from its right side to its left side.
+fn create_cap_vertices<F: CoordFloat>(
+    edge: &Edge<F>,
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<Vec<geo_types::Coord<F>>, OffsetError> {
+    let normal = edge.inwards_normal()?;
+    let right_vertex = edge.next - normal * distance;
+    let left_vertex = edge.next + normal * distance;
+
+    let mut vertices = Vec::new();
+    match options.cap_style() {
+        CapStyle::Round => create_arc(
+            &mut vertices,
+            &edge.next,
+            distance,
+            &right_vertex,
+            &left_vertex,
+            options,
+            false,
+        )?,
+        CapStyle::Flat => {
+            vertices.push(right_vertex);
+            vertices.push(left_vertex);
+        }
+        CapStyle::Square => {
+            let extension = geo_types::Coord::from((normal.y, -normal.x)) * distance;
+            vertices.push(right_vertex);
+            vertices.push(right_vertex + extension);
+            vertices.push(left_vertex + extension);
+            vertices.push(left_vertex);
+        }
+    }
+    Ok(vertices)
+}
+
+/// Returns the point where the edges offset along the given unit normals meet, unless it lies further than `limit` times `distance` from `vertex`.
+pub(crate) fn create_miter<F: CoordFloat>(
+    vertex: &geo_types::Coord<F>,
+    start_normal: &geo_types::Coord<F>,
+    end_normal: &geo_types::Coord<F>,
+    distance: F,
+    limit: F,
+) -> Option<geo_types::Coord<F>> {
+    let bisector = *start_normal + *end_normal;
+    let bisector_length_squared = bisector.x * bisector.x + bisector.y * bisector.y;
+    let two = F::one() + F::one();
+    if two * two <= limit * limit * bisector_length_squared {
+        Some(*vertex + bisector * (two * distance / bisector_length_squared))
+    } else {
+        None
+    }
+}
+
+/// Reverses `ring` unless it already goes in the requested direction.
+pub(crate) fn oriented<F: CoordFloat>(
+    mut ring: geo_types::LineString<F>,
+    counter_clockwise: bool,
+) -> geo_types::LineString<F> {
+    ring.close();
+    if is_counter_clockwise(&ring) != counter_clockwise {
+        ring.0.reverse();
+    }
+    ring
+}
+
+/// Returns whether `ring` turns counter-clockwise, closing it if needed.
+fn is_counter_clockwise<F: CoordFloat>(ring: &geo_types::LineString<F>) -> bool {
+    let closing_line = match (ring.0.last(), ring.0.first()) {
+        (Some(last), Some(first)) => Some(geo_types::Line::new(*last, *first)),
+        _ => None,
+    };
+    let signed_area = ring
+        .lines()
+        .chain(closing_line)
+        .fold(F::zero(), |area, line| {
+            area + line.start.x * line.end.y - line.end.x * line.start.y
+        });
+    signed_area.is_sign_positive()
+}
+
+/// Returns the coordinates without consecutive duplicates, the edges joining them, and the index of each edge
+/// within `coords`.
+pub(crate) fn distinct_edges<F: CoordFloat>(
+    coords: &[geo_types::Coord<F>],
+) -> (Vec<geo_types::Coord<F>>, Vec<Edge<F>>, Vec<usize>) {
+    let mut distinct_coords: Vec<geo_types::Coord<F>> = Vec::new();
+    let mut edge_indices = Vec::new();
+    for (index, coord) in coords.iter().enumerate() {
+        if distinct_coords.last() != Some(coord) {
+            if !distinct_coords.is_empty() {
+                edge_indices.push(index - 1);
+            }
+            distinct_coords.push(*coord);
+        }
+    }
+    let edges = distinct_coords
+        .windows(2)
+        .map(|vertices| Edge::new(&vertices[0], &vertices[1]))
+        .collect();
+    (distinct_coords, edges, edge_indices)
+}
+
+pub(crate) fn create_arc<F: CoordFloat>(
     vertices: &mut Vec<geo_types::Coord<F>>,
     center: &geo_types::Coord<F>,
     radius: F,
     start_vertex: &geo_types::Coord<F>,
     end_vertex: &geo_types::Coord<F>,
-    arc_resolution: ArcResolution<F>,
+    options: &OffsetOptions<F>,
     outwards: bool,
-) {
+) -> Result<(), OffsetError> {
     let tau = F::from(std::f64::consts::TAU).unwrap();
 
     let start_angle = (start_vertex.y - center.y).atan2(start_vertex.x - center.x);
@@ -329,24 +1175,83 @@ fn create_arc<F: CoordFloat>(
         start_angle + tau - end_angle
     };
 
-    let segment_count = match arc_resolution {
-        ArcResolution::SegmentCount(segment_count) => segment_count,
-        ArcResolution::SegmentLength(segment_length) => {
-            let arc_length = angle * radius;
-            (arc_length / segment_length).to_usize().unwrap()
-        },
-    };
+    let angle = if outwards { angle } else { tau - angle };
 
-    let segment_angle =
-        if outwards { -angle } else { tau - angle } / F::from(segment_count).unwrap();
+    let sweep = if outwards { -angle } else { angle };
+    let angles: Vec<F> = if options.anchored_arcs() {
+        // Angles of the grid strictly inside the arc, skipping those merging with its ends.
+        let segment_count = options.arc_segment_count(radius, tau)?.max(3);
+        // Steps of the grid are counted with signed integers.
+        if segment_count > isize::MAX as usize {
+            return Err(OffsetError::InfiniteSegmentCount);
+        }
+        let segment_count = segment_count as isize;
+        let segment_angle = tau / F::from(segment_count).unwrap();
+        let tolerance = segment_angle * F::epsilon().sqrt();
+        let low_angle = start_angle.min(start_angle + sweep) + tolerance;
+        let high_angle = start_angle.max(start_angle + sweep) - tolerance;
+        let step = |angle: F| angle.to_isize().ok_or(OffsetError::InfiniteSegmentCount);
+        let first_step = step((low_angle / segment_angle).floor())? + 1;
+        let last_step = step((high_angle / segment_angle).ceil())? - 1;
+        let angles = (first_step..=last_step).map(|step| {
+            // Steps wrap around so that shared vertices have identical coordinates.
+            let step = step.rem_euclid(segment_count);
+            segment_angle * F::from(step).unwrap()
+        });
+        if outwards {
+            angles.rev().collect()
+        } else {
+            angles.collect()
+        }
+    } else {
+        let segment_count = options.arc_segment_count(radius, angle)?;
+        let segment_count = match options.arc_approximation() {
+            ArcApproximation::Inscribed => segment_count,
+            ArcApproximation::Circumscribed => {
+                // Segments spanning at most a third of a circle keep vertices within twice the radius.
+                let min_segment_count = (angle * F::from(3.0).unwrap() / tau).ceil();
+                segment_count.max(min_segment_count.to_usize().unwrap())
+            }
+        };
+        let segment_angle = sweep / F::from(segment_count).unwrap();
+        (1..segment_count)
+            .map(|i| start_angle + segment_angle * F::from(i).unwrap())
+            .collect()
+    };
 
     vertices.push(*start_vertex);
-    for i in 1..segment_count {
-        let angle = start_angle + segment_angle * F::from(i).unwrap();
-        vertices.push(geo_types::Coord::from((
-            center.x + angle.cos() * radius,
-            center.y + angle.sin() * radius,
-        )));
+    match options.arc_approximation() {
+        ArcApproximation::Inscribed => {
+            for angle in angles {
+                vertices.push(geo_types::Coord::from((
+                    center.x + angle.cos() * radius,
+                    center.y + angle.sin() * radius,
+                )));
+            }
+        }
+        ArcApproximation::Circumscribed => {
+            // Vertices are the intersections of the tangents at consecutive angles.
+            let tangent_angles = std::iter::once(start_angle)
+                .chain(angles)
+                .chain(std::iter::once(start_angle + sweep));
+            let pi = F::from(std::f64::consts::PI).unwrap();
+            for (angle, next_angle) in tangent_angles.tuple_windows() {
+                // Anchored angles wrap around, but segments span less than half a circle.
+                let segment_angle = match next_angle - angle {
+                    segment_angle if segment_angle > pi => segment_angle - tau,
+                    segment_angle if segment_angle < -pi => segment_angle + tau,
+                    segment_angle => segment_angle,
+                };
+                let half_segment_angle = segment_angle / (F::one() + F::one());
+                let vertex_radius = radius / half_segment_angle.cos();
+                let angle = angle + half_segment_angle;
+                vertices.push(geo_types::Coord::from((
+                    center.x + angle.cos() * vertex_radius,
+                    center.y + angle.sin() * vertex_radius,
+                )));
+            }
+        }
     }
     vertices.push(*end_vertex);
+    Ok(())
 }
@@ -0,0 +1,669 @@
+use super::*;
+use crate::extent::checked_magnitude;
+use crate::offset::{create_arc, create_miter, distinct_edges, oriented};
+use geo_types::CoordFloat;
+use itertools::Itertools;
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+use std::collections::HashMap;
+
+/// Computes curves running parallel to linear geometries.
+pub trait OffsetCurve<F: CoordFloat> {
+    /// Returns the curve lying `distance` away on the left of the geometry, or on its right for negative distances.
+    ///
+    /// Curves follow the direction of the geometry. Parts that would come closer than `distance` to the geometry,
+    /// such as the loops formed inside tight turns, are removed, which can split the curve into several line strings.
+    fn offset_curve(&self, distance: F) -> Result<geo_types::MultiLineString<F>, OffsetError> {
+        self.offset_curve_with_options(distance, &Default::default())
+    }
+
+    /// Computes the offset curve using the given [`OffsetOptions`].
+    ///
+    /// The join style and arc resolution shape the curve around convex vertices.
+    fn offset_curve_with_options(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<geo_types::MultiLineString<F>, OffsetError>;
+}
+
+impl<F: CoordFloat> OffsetCurve<F> for geo_types::MultiLineString<F> {
+    fn offset_curve_with_options(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<geo_types::MultiLineString<F>, OffsetError> {
+        let mut multi_line_string_with_offset = geo_types::MultiLineString::<F>(Vec::new());
+        for (index, line_string) in self.0.iter().enumerate() {
+            let line_string_with_offset = line_string
+                .offset_curve_with_options(distance, options)
+                .map_err(|error| error.at(Location::Member(index)))?;
+            multi_line_string_with_offset
+                .0
+                .extend(line_string_with_offset.0);
+        }
+        Ok(multi_line_string_with_offset)
+    }
+}
+
+impl<F: CoordFloat> OffsetCurve<F> for geo_types::LineString<F> {
+    fn offset_curve_with_options(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<geo_types::MultiLineString<F>, OffsetError> {
+        let magnitude = checked_magnitude(self, distance)?;
+        let (coords, edges, edge_indices) = distinct_edges(&self.0);
+        if coords.len() < 2 {
+            return Ok(geo_types::MultiLineString(Vec::new()));
+        }
+        if distance.is_zero() {
+            return Ok(geo_types::MultiLineString(vec![geo_types::LineString(
+                coords,
+            )]));
+        }
+
+        let closed = self.is_closed();
+        let segments = create_raw_curve(&edges, closed, distance, options)
+            .map_err(|error| error.map_edge(|edge| edge_indices[edge]))?;
+
+        Ok(remove_loops(
+            &edges,
+            &segments,
+            closed,
+            distance.abs(),
+            magnitude,
+        ))
+    }
+}
+
+impl<F: CoordFloat> OffsetCurve<F> for geo_types::Line<F> {
+    fn offset_curve_with_options(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<geo_types::MultiLineString<F>, OffsetError> {
+        geo_types::LineString(vec![self.start, self.end])
+            .offset_curve_with_options(distance, options)
+    }
+}
+
+/// Closed rings bounding an offset area.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour<F: CoordFloat> {
+    /// Outer boundaries, oriented counter-clockwise.
+    pub exteriors: geo_types::MultiLineString<F>,
+    /// Boundaries of holes, oriented clockwise.
+    pub holes: geo_types::MultiLineString<F>,
+}
+
+impl<F: CoordFloat> From<Contour<F>> for geo_types::MultiLineString<F> {
+    /// Gathers exteriors followed by holes.
+    fn from(contour: Contour<F>) -> Self {
+        let mut rings = contour.exteriors;
+        rings.0.extend(contour.holes);
+        rings
+    }
+}
+
+/// Computes the rings bounding offset areas rather than the areas themselves.
+///
+/// ```
+/// # fn main() -> Result<(), geo_offset::OffsetError> {
+/// use geo_offset::OffsetContour;
+/// use geo_types::polygon;
+///
+/// let polygon = polygon![
+///     exterior: [(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.)],
+///     interiors: [[(x: 4., y: 4.), (x: 6., y: 4.), (x: 6., y: 6.), (x: 4., y: 6.)]],
+/// ];
+///
+/// let contour = polygon.offset_contour(-0.5)?;
+/// assert_eq!(contour.exteriors.0.len(), 1);
+/// assert_eq!(contour.holes.0.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub trait OffsetContour<F: CoordFloat> {
+    /// Returns the rings bounding the area computed by [`Offset::offset`].
+    fn offset_contour(&self, distance: F) -> Result<Contour<F>, OffsetError> {
+        self.offset_contour_with_options(distance, &Default::default())
+    }
+
+    /// Computes the offset contour using the given [`OffsetOptions`].
+    fn offset_contour_with_options(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<Contour<F>, OffsetError>;
+}
+
+impl<F: CoordFloat, T: Offset<F>> OffsetContour<F> for T {
+    fn offset_contour_with_options(
+        &self,
+        distance: F,
+        options: &OffsetOptions<F>,
+    ) -> Result<Contour<F>, OffsetError> {
+        let mut contour = Contour {
+            exteriors: geo_types::MultiLineString(Vec::new()),
+            holes: geo_types::MultiLineString(Vec::new()),
+        };
+        for polygon in self.offset_with_options(distance, options)? {
+            let (exterior, interiors) = polygon.into_inner();
+            contour.exteriors.0.push(oriented(exterior, true));
+            contour.holes.0.extend(
+                interiors
+                    .into_iter()
+                    .map(|interior| oriented(interior, false)),
+            );
+        }
+        Ok(contour)
+    }
+}
+
+/// Part of the original geometry a raw curve segment is derived from.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Source<F: CoordFloat> {
+    /// The segment is parallel to the edge at this index.
+    Edge(usize),
+    /// The segment goes around this vertex.
+    Vertex(geo_types::Coord<F>),
+    /// The segment links offset edges through their common vertex and never belongs to the final curve.
+    Connector,
+}
+
+/// Segment of an offset curve before self-intersections are removed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct RawSegment<F: CoordFloat> {
+    pub(crate) edge: Edge<F>,
+    pub(crate) source: Source<F>,
+}
+
+/// Offsets each edge by `distance` on its left, or on its right for negative distances, and links consecutive
+/// offset edges.
+///
+/// Around convex vertices the offset edges are linked according to the join style.
+/// Around concave vertices they are linked through the vertex itself, so that the curve crosses itself there.
+pub(crate) fn create_raw_curve<F: CoordFloat>(
+    edges: &[Edge<F>],
+    closed: bool,
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<Vec<RawSegment<F>>, OffsetError> {
+    let side = if distance.is_sign_negative() {
+        -F::one()
+    } else {
+        F::one()
+    };
+    let distance = distance.abs();
+
+    let offset_edges = edges
+        .iter()
+        .enumerate()
+        .map(|(index, edge)| {
+            let normal = edge
+                .inwards_normal()
+                .map_err(|error| OffsetError::from(error).at(Location::Edge(index)))?
+                * side;
+            Ok(edge.with_offset(normal.x * distance, normal.y * distance))
+        })
+        .collect::<Result<Vec<_>, OffsetError>>()?;
+
+    let mut segments = Vec::new();
+    for (index, offset_edge) in offset_edges.iter().enumerate() {
+        segments.push(RawSegment {
+            edge: *offset_edge,
+            source: Source::Edge(index),
+        });
+
+        let next_index = index + 1;
+        if next_index < edges.len() || closed {
+            let next_index = next_index % edges.len();
+            let mut vertices = Vec::new();
+            let source = create_raw_join(
+                &mut vertices,
+                &edges[index],
+                &edges[next_index],
+                offset_edge,
+                &offset_edges[next_index],
+                side,
+                distance,
+                options,
+            )
+            .map_err(|error| error.at(Location::Edge(index)))?;
+            segments.extend(vertices.windows(2).map(|vertices| RawSegment {
+                edge: Edge::new(&vertices[0], &vertices[1]),
+                source,
+            }));
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Returns the vertices of a raw curve, in order.
+///
+/// Offset edges linked through a concave vertex are cut where they cross instead, so that the curve does not loop
+/// around the vertex, as long as the area left out is covered by the buffers of both edges. These buffers only
+/// extend on the offset side when `single_sided` is set.
+pub(crate) fn raw_curve_vertices<F: CoordFloat>(
+    segments: &[RawSegment<F>],
+    closed: bool,
+    single_sided: bool,
+) -> Vec<geo_types::Coord<F>> {
+    let segments = cut_connectors(segments, single_sided);
+    let mut vertices: Vec<_> = segments
+        .iter()
+        .map(|segment| segment.edge.current)
+        .collect();
+    if closed {
+        vertices.extend(vertices.first().copied());
+    } else {
+        vertices.extend(segments.last().map(|segment| segment.edge.next));
+    }
+    vertices
+}
+
+/// Returns the rings of a closed raw curve, in order.
+///
+/// Connectors are cut as for [`raw_curve_vertices`]. The curve is then split into simple loops where it crosses
+/// itself, and the loops made only of parts closer to the original edges than to their source are left out. Such
+/// loops lie within the buffer but turn the wrong way around it, as when a side of a ring collapses.
+pub(crate) fn raw_curve_rings<F: CoordFloat>(
+    edges: &[Edge<F>],
+    segments: &[RawSegment<F>],
+    single_sided: bool,
+    distance: F,
+    magnitude: F,
+) -> Vec<Vec<geo_types::Coord<F>>> {
+    let segments = cut_connectors(segments, single_sided);
+    let splits = split_at_crossings(&segments, true);
+    let is_closer = closer_edge_test(edges, distance, magnitude);
+    let two = F::one() + F::one();
+    let key = |coord: &geo_types::Coord<F>| {
+        let bits = |value: F| (value.to_f64().unwrap_or(f64::NAN) + 0.0).to_bits();
+        (bits(coord.x), bits(coord.y))
+    };
+
+    // Vertices of the pending loop, with whether the part leading to them is closer to another edge, unless they
+    // come from connectors.
+    let mut vertices: Vec<(geo_types::Coord<F>, Option<bool>)> = Vec::new();
+    let mut positions = HashMap::new();
+    let mut rings = Vec::new();
+    let mut close_loop = |vertices: &[(geo_types::Coord<F>, Option<bool>)]| {
+        let mut checked = vertices[1..].iter().filter_map(|(_, closer)| *closer);
+        let is_within_buffer = checked.next() == Some(true) && checked.all(|closer| closer);
+        if vertices.len() > 3 && !is_within_buffer {
+            rings.push(vertices.iter().map(|(vertex, _)| *vertex).collect());
+        }
+    };
+    for (segment, split) in segments.iter().zip(splits) {
+        let segment_vertices = std::iter::once(segment.edge.current)
+            .chain(split.into_iter().map(|(_, intersection)| intersection))
+            .chain(std::iter::once(segment.edge.next));
+        for (start, end) in segment_vertices.tuple_windows() {
+            if vertices.is_empty() {
+                positions.insert(key(&start), 0);
+                vertices.push((start, None));
+            }
+            if start == end {
+                continue;
+            }
+
+            let closer = match segment.source {
+                Source::Connector => None,
+                source => Some(is_closer(&((start + end) / two), source)),
+            };
+            vertices.push((end, closer));
+            // Coming back to a vertex closes a loop, which is taken out of the pending one.
+            if let Some(&position) = positions.get(&key(&end)) {
+                close_loop(&vertices[position..]);
+                for (vertex, _) in vertices.drain(position + 1..) {
+                    positions.remove(&key(&vertex));
+                }
+            }
+            positions.insert(key(&end), vertices.len() - 1);
+        }
+    }
+    if vertices.len() > 1 {
+        vertices.push((vertices[0].0, None));
+        close_loop(&vertices);
+    }
+    rings
+}
+
+/// Cuts offset edges linked through a concave vertex where they cross, as described for [`raw_curve_vertices`].
+fn cut_connectors<F: CoordFloat>(
+    segments: &[RawSegment<F>],
+    single_sided: bool,
+) -> Vec<RawSegment<F>> {
+    let mut cut_segments: Vec<RawSegment<F>> = Vec::new();
+    let mut index = 0;
+    while index < segments.len() {
+        let segment = &segments[index];
+        // Connectors come in pairs, between the offset edges of the vertex.
+        if segment.source == Source::Connector && index > 0 && index + 1 < segments.len() {
+            let previous_edge = &segments[index - 1].edge;
+            let next_index = (index + 2) % segments.len();
+            let next_segment = &segments[next_index];
+            let crossing = intersect(previous_edge, &next_segment.edge).filter(|_| {
+                is_covered_twice(
+                    previous_edge,
+                    &next_segment.edge,
+                    &segment.edge.next,
+                    single_sided,
+                )
+            });
+            if let Some((_, _, crossing)) = crossing {
+                if let Some(previous_segment) = cut_segments.last_mut() {
+                    previous_segment.edge.next = crossing;
+                }
+                if next_index == 0 {
+                    cut_segments[0].edge.current = crossing;
+                } else {
+                    cut_segments.push(RawSegment {
+                        edge: Edge::new(&crossing, &next_segment.edge.next),
+                        source: next_segment.source,
+                    });
+                }
+                index += 3;
+            } else {
+                cut_segments.extend_from_slice(&segments[index..index + 2]);
+                index += 2;
+            }
+            continue;
+        }
+        cut_segments.push(*segment);
+        index += 1;
+    }
+    cut_segments
+}
+
+/// Returns whether the ends of two crossing offset edges around `vertex` lie in the buffer of the other edge.
+fn is_covered_twice<F: CoordFloat>(
+    previous_edge: &Edge<F>,
+    next_edge: &Edge<F>,
+    vertex: &geo_types::Coord<F>,
+    single_sided: bool,
+) -> bool {
+    let is_beside = |point: &geo_types::Coord<F>, edge: &Edge<F>| {
+        let direction = edge.next - edge.current;
+        let projection = dot(&(*point - edge.current), &direction);
+        projection >= F::zero() && projection <= dot(&direction, &direction)
+    };
+    let is_on_offset_side = !single_sided
+        || dot(
+            &(previous_edge.next - *vertex),
+            &(next_edge.current - *vertex),
+        ) >= F::zero();
+    is_on_offset_side
+        && is_beside(&previous_edge.next, next_edge)
+        && is_beside(&next_edge.current, previous_edge)
+}
+
+/// Pushes the vertices linking two consecutive offset edges and returns where they come from.
+#[allow(clippy::too_many_arguments)]
+fn create_raw_join<F: CoordFloat>(
+    vertices: &mut Vec<geo_types::Coord<F>>,
+    previous_edge: &Edge<F>,
+    next_edge: &Edge<F>,
+    previous_offset_edge: &Edge<F>,
+    next_offset_edge: &Edge<F>,
+    side: F,
+    distance: F,
+    options: &OffsetOptions<F>,
+) -> Result<Source<F>, OffsetError> {
+    let vertex = previous_edge.next;
+    let start_vertex = previous_offset_edge.next;
+    let end_vertex = next_offset_edge.current;
+
+    // Positive when the line turns towards the offset side.
+    let turn = cross(
+        &(previous_edge.next - previous_edge.current),
+        &(next_edge.next - next_edge.current),
+    ) * side;
+
+    if length(&(end_vertex - start_vertex)) <= F::epsilon().sqrt() * distance {
+        vertices.push(start_vertex);
+        vertices.push(end_vertex);
+        return Ok(Source::Vertex(vertex));
+    }
+
+    if turn > F::zero() {
+        vertices.push(start_vertex);
+        vertices.push(vertex);
+        vertices.push(end_vertex);
+        return Ok(Source::Connector);
+    }
+
+    match options.join_style() {
+        JoinStyle::Round => create_arc(
+            vertices,
+            &vertex,
+            distance,
+            &start_vertex,
+            &end_vertex,
+            options,
+            side > F::zero(),
+        )?,
+        JoinStyle::Miter { limit } => {
+            vertices.push(start_vertex);
+            vertices.extend(create_miter(
+                &vertex,
+                &((start_vertex - vertex) / distance),
+                &((end_vertex - vertex) / distance),
+                distance,
+                limit,
+            ));
+            vertices.push(end_vertex);
+        }
+        JoinStyle::Bevel => {
+            vertices.push(start_vertex);
+            vertices.push(end_vertex);
+        }
+    }
+    Ok(Source::Vertex(vertex))
+}
+
+/// Splits the raw curve where it crosses itself and keeps the parts which are not closer to the original edges
+/// than to the part of the geometry they derive from.
+fn remove_loops<F: CoordFloat>(
+    edges: &[Edge<F>],
+    segments: &[RawSegment<F>],
+    closed: bool,
+    distance: F,
+    magnitude: F,
+) -> geo_types::MultiLineString<F> {
+    let splits = split_at_crossings(segments, closed);
+    let is_closer = closer_edge_test(edges, distance, magnitude);
+    let two = F::one() + F::one();
+    let mut line_strings: Vec<Vec<geo_types::Coord<F>>> = Vec::new();
+    for (segment, split) in segments.iter().zip(splits) {
+        if segment.source == Source::Connector {
+            continue;
+        }
+
+        let vertices = std::iter::once(segment.edge.current)
+            .chain(split.into_iter().map(|(_, intersection)| intersection))
+            .chain(std::iter::once(segment.edge.next));
+
+        for (start, end) in vertices.tuple_windows() {
+            if start == end || is_closer(&((start + end) / two), segment.source) {
+                continue;
+            }
+
+            match line_strings.last_mut() {
+                Some(line_string) if line_string.last() == Some(&start) => line_string.push(end),
+                _ => line_strings.push(vec![start, end]),
+            }
+        }
+    }
+
+    let ring_start = line_strings
+        .first()
+        .and_then(|line_string| line_string.first());
+    let ring_end = line_strings
+        .last()
+        .and_then(|line_string| line_string.last());
+    if closed && line_strings.len() > 1 && ring_start == ring_end {
+        let first = line_strings.remove(0);
+        if let Some(last) = line_strings.last_mut() {
+            last.extend(first.into_iter().skip(1));
+        }
+    }
+
+    geo_types::MultiLineString(
+        line_strings
+            .into_iter()
+            .map(geo_types::LineString)
+            .collect(),
+    )
+}
+
+/// Returns the positions along each segment and the locations where the raw curve crosses itself, in order.
+fn split_at_crossings<F: CoordFloat>(
+    segments: &[RawSegment<F>],
+    closed: bool,
+) -> Vec<Vec<(F, geo_types::Coord<F>)>> {
+    let segment_tree = edge_tree(segments.iter().map(|segment| &segment.edge));
+    let mut pairs: Vec<_> = segments
+        .iter()
+        .enumerate()
+        .flat_map(|(i, segment)| {
+            segment_tree
+                .locate_in_envelope_intersecting(&edge_envelope(&segment.edge))
+                .map(move |other| (i, other.data))
+        })
+        .filter(|&(i, j)| j >= i + 2 && !(closed && i == 0 && j == segments.len() - 1))
+        .collect();
+    pairs.sort_unstable();
+
+    let mut splits = vec![Vec::new(); segments.len()];
+    for (i, j) in pairs {
+        if let Some((t, u, intersection)) = intersect(&segments[i].edge, &segments[j].edge) {
+            splits[i].push((t, intersection));
+            splits[j].push((u, intersection));
+        }
+    }
+    for split in &mut splits {
+        split.sort_by(|(t1, _), (t2, _)| t1.partial_cmp(t2).unwrap_or(std::cmp::Ordering::Equal));
+    }
+    splits
+}
+
+/// Returns a test of whether a point of the raw curve is closer to one of `edges` than to the part of the geometry
+/// it derives from. Points derived from connectors are never closer.
+fn closer_edge_test<'a, F: CoordFloat>(
+    edges: &'a [Edge<F>],
+    distance: F,
+    magnitude: F,
+) -> impl Fn(&geo_types::Coord<F>, Source<F>) -> bool + 'a {
+    let tolerance =
+        F::epsilon().sqrt() * distance + F::epsilon() * F::from(16.0).unwrap() * magnitude;
+    let edge_tree = edge_tree(edges);
+    move |point, source| {
+        let source_distance = match source {
+            Source::Edge(index) => distance_to_edge(point, &edges[index]),
+            Source::Vertex(vertex) => length(&(*point - vertex)),
+            Source::Connector => return false,
+        };
+        // Edges closer than the source lie within a box around the point.
+        let is_closer = |edge: &GeomWithData<Rectangle<[f64; 2]>, usize>| {
+            distance_to_edge(point, &edges[edge.data]) + tolerance < source_distance
+        };
+        edge_tree
+            .locate_in_envelope_intersecting(&envelope(point, point, source_distance))
+            .any(is_closer)
+    }
+}
+
+/// Returns an index of the bounding boxes of `edges`, tagged with their position.
+fn edge_tree<'a, F: CoordFloat + 'a>(
+    edges: impl IntoIterator<Item = &'a Edge<F>>,
+) -> RTree<GeomWithData<Rectangle<[f64; 2]>, usize>> {
+    RTree::bulk_load(
+        edges
+            .into_iter()
+            .enumerate()
+            .map(|(index, edge)| {
+                GeomWithData::new(Rectangle::from_aabb(edge_envelope(edge)), index)
+            })
+            .collect(),
+    )
+}
+
+fn edge_envelope<F: CoordFloat>(edge: &Edge<F>) -> AABB<[f64; 2]> {
+    envelope(&edge.current, &edge.next, F::zero())
+}
+
+/// Returns the bounding box of `first` and `second` grown by `margin` on every side.
+fn envelope<F: CoordFloat>(
+    first: &geo_types::Coord<F>,
+    second: &geo_types::Coord<F>,
+    margin: F,
+) -> AABB<[f64; 2]> {
+    let corner = |x: F, y: F| {
+        [
+            x.to_f64().unwrap_or(f64::NAN),
+            y.to_f64().unwrap_or(f64::NAN),
+        ]
+    };
+    AABB::from_corners(
+        corner(
+            first.x.min(second.x) - margin,
+            first.y.min(second.y) - margin,
+        ),
+        corner(
+            first.x.max(second.x) + margin,
+            first.y.max(second.y) + margin,
+        ),
+    )
+}
+
+/// Returns the positions along both edges and the location of their intersection, if any.
+fn intersect<F: CoordFloat>(
+    first: &Edge<F>,
+    second: &Edge<F>,
+) -> Option<(F, F, geo_types::Coord<F>)> {
+    let first_direction = first.next - first.current;
+    let second_direction = second.next - second.current;
+    let denominator = cross(&first_direction, &second_direction);
+    if denominator.is_zero() {
+        return None;
+    }
+
+    let offset = second.current - first.current;
+    let t = cross(&offset, &second_direction) / denominator;
+    let u = cross(&offset, &first_direction) / denominator;
+    let range = F::zero()..=F::one();
+    if range.contains(&t) && range.contains(&u) {
+        Some((t, u, first.current + first_direction * t))
+    } else {
+        None
+    }
+}
+
+fn distance_to_edge<F: CoordFloat>(point: &geo_types::Coord<F>, edge: &Edge<F>) -> F {
+    let direction = edge.next - edge.current;
+    let length_squared = dot(&direction, &direction);
+    let t = if length_squared.is_zero() {
+        F::zero()
+    } else {
+        (dot(&(*point - edge.current), &direction) / length_squared)
+            .max(F::zero())
+            .min(F::one())
+    };
+    length(&(*point - (edge.current + direction * t)))
+}
+
+fn cross<F: CoordFloat>(a: &geo_types::Coord<F>, b: &geo_types::Coord<F>) -> F {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot<F: CoordFloat>(a: &geo_types::Coord<F>, b: &geo_types::Coord<F>) -> F {
+    a.x * b.x + a.y * b.y
+}
+
+fn length<F: CoordFloat>(vector: &geo_types::Coord<F>) -> F {
+    vector.x.hypot(vector.y)
+}
@@ -0,0 +1,548 @@
+use super::*;
+use crate::cluster::overlapping_clusters;
+use crate::extent::{checked_magnitude, Extent};
+use geo_types::CoordFloat;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use std::sync::Arc;
+
+/// Largest coordinate magnitude Clipper accepts once scaled to integers.
+pub(crate) const CLIPPER_HI_RANGE: f64 = 4_611_686_018_427_387_903.0;
+
+/// Largest number of segments generated for a single arc, above which the arc resolution is rejected.
+const MAX_ARC_SEGMENT_COUNT: usize = 1 << 20;
+
+/// Factor applied to coordinates before boolean operations.
+///
+/// Boolean operations are computed on integer coordinates, so coordinates are snapped to `1 / factor` units.
+///
+/// ```
+/// # use geo_offset::ScaleFactor;
+/// // The default factor snaps coordinates to 0.001 units.
+/// let scale_factor: ScaleFactor<f64> = Default::default();
+/// assert_eq!(scale_factor, ScaleFactor::Fixed(1000.0));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ScaleFactor<F: CoordFloat> {
+    /// Uses the given factor whatever the geometry.
+    Fixed(F),
+    /// Chooses the largest power of two keeping the geometry and its offset within the integer range.
+    ///
+    /// The range is bounded by the mantissa of `F` since larger integers could not be represented exactly.
+    Auto,
+}
+
+impl<F: CoordFloat> ScaleFactor<F> {
+    /// Returns the factor to use for coordinates up to `magnitude` in absolute value.
+    ///
+    /// ```
+    /// # use geo_offset::ScaleFactor;
+    /// assert_eq!(ScaleFactor::Fixed(1000.0).for_magnitude(1.0), 1000.0);
+    /// assert_eq!(ScaleFactor::Auto.for_magnitude(1.0), 2f64.powi(53));
+    /// assert_eq!(ScaleFactor::Auto.for_magnitude(180.0), 2f64.powi(45));
+    /// ```
+    pub fn for_magnitude(&self, magnitude: F) -> F {
+        match *self {
+            ScaleFactor::Fixed(factor) => factor,
+            ScaleFactor::Auto => {
+                let range = (F::from(2.0).unwrap() / F::epsilon())
+                    .min(F::from(CLIPPER_HI_RANGE).unwrap_or_else(F::max_value));
+                if magnitude.is_normal() {
+                    F::from(2.0)
+                        .unwrap()
+                        .powf((range / magnitude).log2().floor())
+                } else {
+                    F::one()
+                }
+            }
+        }
+    }
+}
+
+impl<F: CoordFloat> Default for ScaleFactor<F> {
+    fn default() -> Self {
+        ScaleFactor::Fixed(F::from(1000.0).unwrap())
+    }
+}
+
+/// Method used to offset polygons and line strings.
+///
+/// ```
+/// # use geo_offset::OffsetAlgorithm;
+/// // Offsets are built from pieces merged by the backend by default.
+/// let algorithm: OffsetAlgorithm = Default::default();
+/// assert_eq!(algorithm, OffsetAlgorithm::Union);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OffsetAlgorithm {
+    /// Merges rectangles, joins and caps built around every edge with the [`OffsetBackend`].
+    #[default]
+    Union,
+    /// Delegates to the polygon offsetting of the Clipper library.
+    ///
+    /// The arc resolution is converted to the largest distance Clipper allows between arcs and their segments.
+    /// Clipper spreads segments evenly around full circles, so a segment count applies to full circles rather than to each arc.
+    /// Clipper squares off corners over the miter limit and has no bevel joins, so [`JoinStyle::Bevel`] fails with
+    /// [`OffsetError::BackendFailure`].
+    ///
+    /// Other geometries and single-sided buffers are still computed with [`OffsetAlgorithm::Union`].
+    #[cfg(feature = "clipper")]
+    Clipper,
+}
+
+/// Parameters controlling how offsets are computed.
+///
+/// Options are built from their default value by chaining `with_*` methods.
+///
+/// ```
+/// # use geo_offset::{ArcResolution, OffsetOptions, ScaleFactor};
+/// let options = OffsetOptions::<f64>::new()
+///     .with_scale_factor(1e7)
+///     .with_arc_resolution(ArcResolution::SegmentLength(0.5));
+/// assert_eq!(options.scale_factor(), ScaleFactor::Fixed(1e7));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OffsetOptions<F: CoordFloat> {
+    algorithm: OffsetAlgorithm,
+    backend: Arc<dyn OffsetBackend<F>>,
+    scale_factor: ScaleFactor<F>,
+    arc_resolution: ArcResolution<F>,
+    arc_segment_bounds: (usize, usize),
+    arc_approximation: ArcApproximation,
+    anchored_arcs: bool,
+    join_style: JoinStyle<F>,
+    cap_style: CapStyle,
+    single_sided: bool,
+    lenient: bool,
+    sanitized_input: bool,
+    validated_input: bool,
+}
+
+impl<F: CoordFloat> OffsetOptions<F> {
+    /// Creates options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the method used to offset polygons and line strings.
+    pub fn with_algorithm(mut self, algorithm: OffsetAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Sets the implementation of boolean operations.
+    ///
+    /// The default backend is returned by [`default_backend`].
+    pub fn with_backend(mut self, backend: impl OffsetBackend<F> + 'static) -> Self {
+        self.backend = Arc::new(backend);
+        self
+    }
+
+    /// Sets a fixed factor applied to coordinates before boolean operations.
+    ///
+    /// The default factor is `1000.0`.
+    pub fn with_scale_factor(mut self, scale_factor: F) -> Self {
+        self.scale_factor = ScaleFactor::Fixed(scale_factor);
+        self
+    }
+
+    /// Derives the factor applied to coordinates from the extent of each geometry and the offset distance.
+    ///
+    /// See [`ScaleFactor::Auto`].
+    pub fn with_auto_scale_factor(mut self) -> Self {
+        self.scale_factor = ScaleFactor::Auto;
+        self
+    }
+
+    /// Sets the resolution of generated arcs.
+    pub fn with_arc_resolution(mut self, arc_resolution: ArcResolution<F>) -> Self {
+        self.arc_resolution = arc_resolution;
+        self
+    }
+
+    /// Sets the smallest and largest number of segments of each generated arc.
+    ///
+    /// Counts derived from the [`ArcResolution`] are clamped to these bounds, which keeps short
+    /// arcs from collapsing and huge arcs from producing unbounded numbers of vertices.
+    /// Arcs have at least one segment and circles at least three.
+    /// The bounds are not applied by the Clipper algorithm, which spreads segments around full circles.
+    ///
+    /// The default bounds are `1` and `usize::MAX`. Counts still above 2^20 once clamped fail with
+    /// [`OffsetError::InfiniteSegmentCount`] rather than exhaust memory.
+    ///
+    /// ```
+    /// # use geo_offset::{ArcResolution, Offset, OffsetOptions};
+    /// let options = OffsetOptions::new()
+    ///     .with_arc_resolution(ArcResolution::SegmentLength(1e-3))
+    ///     .with_arc_segment_bounds(8, 64);
+    /// let circle = geo_types::Point::new(0.0, 0.0).offset_with_options(1e3, &options)?;
+    /// assert_eq!(circle.0[0].exterior().lines().count(), 64);
+    /// # Ok::<(), geo_offset::OffsetError>(())
+    /// ```
+    pub fn with_arc_segment_bounds(mut self, min: usize, max: usize) -> Self {
+        self.arc_segment_bounds = (min.max(1), max.max(min).max(1));
+        self
+    }
+
+    /// Sets the placement of arc segments relative to the true arcs.
+    ///
+    /// The Clipper algorithm always places vertices on the true arcs.
+    pub fn with_arc_approximation(mut self, arc_approximation: ArcApproximation) -> Self {
+        self.arc_approximation = arc_approximation;
+        self
+    }
+
+    /// Sets whether arc vertices are snapped to a grid of angles anchored at zero.
+    ///
+    /// The grid has the angle spanned by the segments of a full circle with the same radius,
+    /// so that circles and arcs of touching geometries share their vertices and union cleanly.
+    /// Arcs then have as many segments as the grid lines they cross.
+    /// The Clipper algorithm does not anchor arcs.
+    ///
+    /// ```
+    /// # use geo_offset::{ArcResolution, Offset, OffsetOptions};
+    /// let options = OffsetOptions::new()
+    ///     .with_arc_resolution(ArcResolution::SegmentCount(8))
+    ///     .with_anchored_arcs(true);
+    /// let circle = geo_types::Point::new(0.0, 0.0).offset_with_options(1.0, &options)?;
+    /// assert_eq!(circle.0[0].exterior().0[0], geo_types::Coord { x: 1.0, y: 0.0 });
+    /// # Ok::<(), geo_offset::OffsetError>(())
+    /// ```
+    pub fn with_anchored_arcs(mut self, anchored_arcs: bool) -> Self {
+        self.anchored_arcs = anchored_arcs;
+        self
+    }
+
+    /// Sets the shape of corners.
+    pub fn with_join_style(mut self, join_style: JoinStyle<F>) -> Self {
+        self.join_style = join_style;
+        self
+    }
+
+    /// Sets the shape of the ends of lines.
+    pub fn with_cap_style(mut self, cap_style: CapStyle) -> Self {
+        self.cap_style = cap_style;
+        self
+    }
+
+    /// Sets whether lines are buffered on a single side.
+    ///
+    /// Single-sided buffers cover the left of lines, or their right for negative distances.
+    /// They are bounded by the line and its [offset curve](OffsetCurve), so caps are omitted.
+    /// Other geometries are not affected.
+    pub fn with_single_sided(mut self, single_sided: bool) -> Self {
+        self.single_sided = single_sided;
+        self
+    }
+
+    /// Sets whether multi-geometries and collections leave out the members which cannot be offset.
+    ///
+    /// The errors of these members are then reported by [`Offset::offset_with_diagnostics`] instead of failing the
+    /// whole geometry. Errors which do not depend on a member, such as an invalid distance, still do.
+    ///
+    /// ```
+    /// # use geo_offset::{Location, Offset, OffsetOptions};
+    /// # use geo_types::{MultiPoint, Point};
+    /// let points = MultiPoint(vec![Point::new(0.0, 0.0), Point::new(f64::NAN, 0.0)]);
+    /// let options = OffsetOptions::new().with_lenient(true);
+    /// let points_with_offset = points.offset_with_diagnostics(1.0, &options)?;
+    /// assert_eq!(points_with_offset.offset.0.len(), 1);
+    /// assert_eq!(points_with_offset.diagnostics[0].path(), [Location::Member(1), Location::Vertex(0)]);
+    /// # Ok::<(), geo_offset::OffsetError>(())
+    /// ```
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets whether geometries are repaired with [`Sanitize::sanitize`] before being offset.
+    ///
+    /// The repairs are reported by [`Offset::offset_with_diagnostics`]. Diagnostics then locate errors within the
+    /// repaired geometry.
+    ///
+    /// ```
+    /// # use geo_offset::{Offset, OffsetOptions};
+    /// let line_string = geo_types::LineString::from(vec![(0., 0.), (f64::NAN, 0.), (1., 0.)]);
+    /// assert!(line_string.offset(1.0).is_err());
+    /// let options = OffsetOptions::new().with_sanitized_input(true);
+    /// let line_string_with_offset = line_string.offset_with_diagnostics(1.0, &options)?;
+    /// assert_eq!(line_string_with_offset.repairs.len(), 1);
+    /// # Ok::<(), geo_offset::OffsetError>(())
+    /// ```
+    pub fn with_sanitized_input(mut self, sanitized_input: bool) -> Self {
+        self.sanitized_input = sanitized_input;
+        self
+    }
+
+    /// Sets whether polygons are checked with [`Validate::validate`] before being offset.
+    ///
+    /// Offsets of invalid polygons then fail with [`OffsetError::InvalidGeometry`], located at the first broken
+    /// rule. Polygons are checked after being sanitized, see [`OffsetOptions::with_sanitized_input`].
+    /// In lenient mode, polygons overlapping an earlier member of their multi-polygon are left out instead.
+    ///
+    /// ```
+    /// # use geo_offset::{InvalidReason, Offset, OffsetError, OffsetOptions};
+    /// # use geo_types::polygon;
+    /// let bowtie = polygon![(x: 0., y: 0.), (x: 2., y: 2.), (x: 2., y: 0.), (x: 0., y: 2.)];
+    /// let options = OffsetOptions::new().with_validated_input(true);
+    /// let error = bowtie.offset_with_options(1.0, &options).unwrap_err();
+    /// assert_eq!(
+    ///     error.cause(),
+    ///     &OffsetError::InvalidGeometry(InvalidReason::SelfIntersection { edge: 2 })
+    /// );
+    /// ```
+    pub fn with_validated_input(mut self, validated_input: bool) -> Self {
+        self.validated_input = validated_input;
+        self
+    }
+
+    /// Returns the method used to offset polygons and line strings.
+    pub fn algorithm(&self) -> OffsetAlgorithm {
+        self.algorithm
+    }
+
+    /// Returns the implementation of boolean operations.
+    pub fn backend(&self) -> &dyn OffsetBackend<F> {
+        self.backend.as_ref()
+    }
+
+    /// Returns the factor applied to coordinates before boolean operations.
+    pub fn scale_factor(&self) -> ScaleFactor<F> {
+        self.scale_factor
+    }
+
+    /// Returns the resolution of generated arcs.
+    pub fn arc_resolution(&self) -> ArcResolution<F> {
+        self.arc_resolution
+    }
+
+    /// Returns the smallest and largest number of segments of each generated arc.
+    pub fn arc_segment_bounds(&self) -> (usize, usize) {
+        self.arc_segment_bounds
+    }
+
+    /// Returns the placement of arc segments relative to the true arcs.
+    pub fn arc_approximation(&self) -> ArcApproximation {
+        self.arc_approximation
+    }
+
+    /// Returns whether arc vertices are snapped to a grid of angles anchored at zero.
+    pub fn anchored_arcs(&self) -> bool {
+        self.anchored_arcs
+    }
+
+    /// Returns the shape of corners.
+    pub fn join_style(&self) -> JoinStyle<F> {
+        self.join_style
+    }
+
+    /// Returns the shape of the ends of lines.
+    pub fn cap_style(&self) -> CapStyle {
+        self.cap_style
+    }
+
+    /// Returns whether lines are buffered on a single side.
+    pub fn single_sided(&self) -> bool {
+        self.single_sided
+    }
+
+    /// Returns whether multi-geometries and collections leave out the members which cannot be offset.
+    pub fn lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Returns whether geometries are repaired before being offset.
+    pub fn sanitized_input(&self) -> bool {
+        self.sanitized_input
+    }
+
+    /// Returns whether polygons are checked before being offset.
+    pub fn validated_input(&self) -> bool {
+        self.validated_input
+    }
+
+    /// Fixes the scale factor for offsetting `geometry` by `distance`.
+    ///
+    /// Nested geometries offset with the returned options share the factor of their container.
+    ///
+    /// Fails if the distance or a coordinate of the geometry is not finite, or if the arc resolution is invalid.
+    /// In lenient mode, members with coordinates that are not finite are left for their own offset to reject.
+    pub(crate) fn resolve<G: Extent<F>>(
+        &self,
+        geometry: &G,
+        distance: F,
+    ) -> Result<Self, OffsetError> {
+        let magnitude = match checked_magnitude(geometry, distance) {
+            Err(error)
+                if self.lenient && matches!(error.path().first(), Some(Location::Member(_))) =>
+            {
+                geometry.finite_magnitude()
+            }
+            result => result?,
+        };
+        self.arc_resolution.validate()?;
+        Ok(match self.scale_factor {
+            ScaleFactor::Fixed(_) => self.clone(),
+            ScaleFactor::Auto => {
+                let magnitude = magnitude + self.reach(distance);
+                Self {
+                    scale_factor: ScaleFactor::Fixed(self.scale_factor.for_magnitude(magnitude)),
+                    ..self.clone()
+                }
+            }
+        })
+    }
+
+    /// Returns how far from the geometry the vertices of its offset by `distance` may lie.
+    ///
+    /// Miters reach up to their limit times the distance, square caps reach their corners and circumscribed arcs
+    /// keep their vertices within twice the distance.
+    pub(crate) fn reach(&self, distance: F) -> F {
+        let join_factor = match self.join_style {
+            JoinStyle::Miter { limit } => limit.max(F::one()),
+            JoinStyle::Round | JoinStyle::Bevel => F::one(),
+        };
+        let cap_factor = match self.cap_style {
+            CapStyle::Square => F::from(std::f64::consts::SQRT_2).unwrap(),
+            CapStyle::Round | CapStyle::Flat => F::one(),
+        };
+        let arc_factor = match self.arc_approximation {
+            ArcApproximation::Circumscribed => F::from(2.0).unwrap(),
+            ArcApproximation::Inscribed => F::one(),
+        };
+        distance.abs() * join_factor.max(cap_factor).max(arc_factor)
+    }
+
+    /// Returns the number of segments of an arc spanning `angle` radians, within the bounds.
+    pub(crate) fn arc_segment_count(&self, radius: F, angle: F) -> Result<usize, OffsetError> {
+        let (min, max) = self.arc_segment_bounds;
+        let segment_count = self.arc_resolution.segment_count(radius, angle)?;
+        segment_count
+            .max(F::from(min).unwrap())
+            .min(F::from(max).unwrap())
+            .to_usize()
+            .filter(|&segment_count| segment_count <= MAX_ARC_SEGMENT_COUNT)
+            .ok_or(OffsetError::InfiniteSegmentCount)
+    }
+
+    /// Returns the area covered by `subject` or `clip`, computed by the backend.
+    pub(crate) fn union(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        self.backend.union(subject, clip, self.factor())
+    }
+
+    /// Returns the area covered by any of `parts`, computed by the backend.
+    ///
+    /// Only parts whose bounding boxes overlap are passed to the backend, the others are
+    /// concatenated as they are.
+    pub(crate) fn union_all(
+        &self,
+        parts: impl IntoIterator<Item = geo_types::MultiPolygon<F>>,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError>
+    where
+        F: MaybeSendSync,
+    {
+        let mut parts: Vec<_> = parts.into_iter().collect();
+        let clusters: Vec<Vec<_>> = overlapping_clusters(&parts)
+            .into_iter()
+            .map(|cluster| {
+                cluster
+                    .into_iter()
+                    .map(|index| {
+                        std::mem::replace(&mut parts[index], geo_types::MultiPolygon(Vec::new()))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        #[cfg(feature = "rayon")]
+        let clusters = clusters.into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let clusters = clusters.into_iter();
+        let polygons = clusters
+            .map(|members| Ok(self.cascaded_union(members)?.0))
+            .collect::<Result<Vec<_>, OffsetError>>()?;
+        Ok(geo_types::MultiPolygon(
+            polygons.into_iter().flatten().collect(),
+        ))
+    }
+
+    /// Merges `parts` pairwise in a balanced tree rather than folding them one at a time,
+    /// so every polygon takes part in a logarithmic number of unions.
+    ///
+    /// The unions of each level are computed in parallel if the `rayon` feature is enabled.
+    fn cascaded_union(
+        &self,
+        mut parts: Vec<geo_types::MultiPolygon<F>>,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError>
+    where
+        F: MaybeSendSync,
+    {
+        let merge = |mut pair: Vec<geo_types::MultiPolygon<F>>| {
+            let clip = pair.pop().unwrap_or(geo_types::MultiPolygon(Vec::new()));
+            match pair.pop() {
+                Some(subject) => self.union(&subject, &clip),
+                None => Ok(clip),
+            }
+        };
+        while parts.len() > 1 {
+            #[cfg(feature = "rayon")]
+            {
+                parts = parts
+                    .into_par_iter()
+                    .chunks(2)
+                    .map(merge)
+                    .collect::<Result<_, _>>()?;
+            }
+            #[cfg(not(feature = "rayon"))]
+            {
+                let pairs = itertools::Itertools::chunks(parts.into_iter(), 2);
+                parts = pairs
+                    .into_iter()
+                    .map(|pair| merge(pair.collect()))
+                    .collect::<Result<_, _>>()?;
+            }
+        }
+        Ok(parts.pop().unwrap_or(geo_types::MultiPolygon(Vec::new())))
+    }
+
+    /// Returns the area covered by `subject` but not by `clip`, computed by the backend.
+    pub(crate) fn difference(
+        &self,
+        subject: &geo_types::MultiPolygon<F>,
+        clip: &geo_types::MultiPolygon<F>,
+    ) -> Result<geo_types::MultiPolygon<F>, OffsetError> {
+        self.backend.difference(subject, clip, self.factor())
+    }
+
+    /// Returns the factor to pass to boolean operations.
+    pub(crate) fn factor(&self) -> F {
+        match self.scale_factor {
+            ScaleFactor::Fixed(factor) => factor,
+            ScaleFactor::Auto => ScaleFactor::default().for_magnitude(F::zero()),
+        }
+    }
+}
+
+impl<F: CoordFloat> Default for OffsetOptions<F> {
+    fn default() -> Self {
+        Self {
+            algorithm: Default::default(),
+            backend: default_backend(),
+            scale_factor: Default::default(),
+            arc_resolution: Default::default(),
+            arc_segment_bounds: (1, usize::MAX),
+            arc_approximation: Default::default(),
+            anchored_arcs: false,
+            join_style: Default::default(),
+            cap_style: Default::default(),
+            single_sided: false,
+            lenient: false,
+            sanitized_input: false,
+            validated_input: false,
+        }
+    }
+}
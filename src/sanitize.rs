@@ -0,0 +1,328 @@
+//! Repairs of common defects in input geometries.
+//!
+//! Geometries read from real-world files often repeat vertices, hold coordinates which are not numbers or have
+//! rings reduced to a point. These defects make offsets fail or produce artifacts, so they can be repaired beforehand, either by
+//! calling [`Sanitize::sanitize`] or through [`crate::OffsetOptions::with_sanitized_input`].
+
+use crate::Location;
+use geo_types::CoordFloat;
+
+/// Defect repaired by [`Sanitize::sanitize`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RepairKind {
+    /// A vertex with a coordinate which is not finite was removed.
+    NonFiniteVertex,
+    /// A vertex equal to the previous one was removed.
+    DuplicateVertex,
+    /// A vertex lying between its neighbours on the line joining them was removed.
+    CollinearVertex,
+    /// A ring with fewer than three distinct vertices, which encloses no area, was removed.
+    ///
+    /// Removing the exterior of a polygon also removes its interiors, leaving an empty polygon.
+    DegenerateRing,
+}
+
+/// Change made by [`Sanitize::sanitize`] to a part of a geometry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Repair {
+    /// Parts leading to the repaired vertex or ring in the input geometry, from the outermost one.
+    pub path: Vec<Location>,
+    /// The defect which was repaired.
+    pub kind: RepairKind,
+}
+
+/// Geometry returned by [`Sanitize::sanitize`], with the repairs it required.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sanitized<G> {
+    /// The repaired geometry.
+    pub geometry: G,
+    /// The repairs, in the order of the parts of the input geometry.
+    pub repairs: Vec<Repair>,
+}
+
+/// Repairs the defects of geometries which keep them from being offset cleanly.
+///
+/// Members of multi-geometries and collections keep their index, except points with coordinates which are not
+/// finite, which are removed from multi-points. Points, lines, rectangles and triangles are left unchanged, since
+/// they cannot lose a vertex, so offsetting points and lines with such coordinates still fails with
+/// [`crate::OffsetError::NonFiniteCoordinate`].
+/// Rings need no closing since polygons close them on construction.
+///
+/// ```
+/// # use geo_offset::{Location, Repair, RepairKind, Sanitize};
+/// let ring = geo_types::LineString::from(vec![(0., 0.), (1., 0.), (1., 0.), (2., 0.), (2., 2.)]);
+/// let polygon = geo_types::Polygon::new(ring, vec![]).sanitize();
+/// assert_eq!(
+///     polygon.geometry.exterior(),
+///     &geo_types::LineString::from(vec![(0., 0.), (2., 0.), (2., 2.), (0., 0.)])
+/// );
+/// assert_eq!(
+///     polygon.repairs[0],
+///     Repair {
+///         path: vec![Location::Exterior, Location::Vertex(2)],
+///         kind: RepairKind::DuplicateVertex,
+///     }
+/// );
+/// assert_eq!(polygon.repairs[1].kind, RepairKind::CollinearVertex);
+/// ```
+pub trait Sanitize<F: CoordFloat>: Sized {
+    /// Returns a copy of the geometry without its defects, and the list of repairs.
+    fn sanitize(&self) -> Sanitized<Self>;
+}
+
+impl<F: CoordFloat> Sanitize<F> for geo_types::Point<F> {
+    fn sanitize(&self) -> Sanitized<Self> {
+        unchanged(self)
+    }
+}
+
+impl<F: CoordFloat> Sanitize<F> for geo_types::Line<F> {
+    fn sanitize(&self) -> Sanitized<Self> {
+        unchanged(self)
+    }
+}
+
+impl<F: CoordFloat> Sanitize<F> for geo_types::LineString<F> {
+    fn sanitize(&self) -> Sanitized<Self> {
+        let mut repairs = Vec::new();
+        let coords = clean_coords(self.0.iter().copied().enumerate(), false, &mut repairs);
+        Sanitized {
+            geometry: geo_types::LineString(coords.into_iter().map(|(_, coord)| coord).collect()),
+            repairs,
+        }
+    }
+}
+
+impl<F: CoordFloat> Sanitize<F> for geo_types::Polygon<F> {
+    fn sanitize(&self) -> Sanitized<Self> {
+        let (exterior, mut repairs) = sanitize_ring(self.exterior(), Location::Exterior);
+        let exterior = match exterior {
+            Some(exterior) => exterior,
+            None => {
+                return Sanitized {
+                    geometry: geo_types::Polygon::new(
+                        geo_types::LineString(Vec::new()),
+                        Vec::new(),
+                    ),
+                    repairs,
+                }
+            }
+        };
+        let mut interiors = Vec::new();
+        for (index, interior) in self.interiors().iter().enumerate() {
+            let (interior, interior_repairs) = sanitize_ring(interior, Location::Interior(index));
+            interiors.extend(interior);
+            repairs.extend(interior_repairs);
+        }
+        Sanitized {
+            geometry: geo_types::Polygon::new(exterior, interiors),
+            repairs,
+        }
+    }
+}
+
+impl<F: CoordFloat> Sanitize<F> for geo_types::MultiPoint<F> {
+    fn sanitize(&self) -> Sanitized<Self> {
+        let mut repairs = Vec::new();
+        let mut points = Vec::new();
+        for (index, point) in self.0.iter().enumerate() {
+            if point.x().is_finite() && point.y().is_finite() {
+                points.push(*point);
+            } else {
+                repairs.push(Repair {
+                    path: vec![Location::Member(index), Location::Vertex(0)],
+                    kind: RepairKind::NonFiniteVertex,
+                });
+            }
+        }
+        Sanitized {
+            geometry: geo_types::MultiPoint(points),
+            repairs,
+        }
+    }
+}
+
+impl<F: CoordFloat> Sanitize<F> for geo_types::MultiLineString<F> {
+    fn sanitize(&self) -> Sanitized<Self> {
+        let (members, repairs) = sanitize_members(&self.0);
+        Sanitized {
+            geometry: geo_types::MultiLineString(members),
+            repairs,
+        }
+    }
+}
+
+impl<F: CoordFloat> Sanitize<F> for geo_types::MultiPolygon<F> {
+    fn sanitize(&self) -> Sanitized<Self> {
+        let (members, repairs) = sanitize_members(&self.0);
+        Sanitized {
+            geometry: geo_types::MultiPolygon(members),
+            repairs,
+        }
+    }
+}
+
+impl<F: CoordFloat> Sanitize<F> for geo_types::GeometryCollection<F> {
+    fn sanitize(&self) -> Sanitized<Self> {
+        let (members, repairs) = sanitize_members(&self.0);
+        Sanitized {
+            geometry: geo_types::GeometryCollection(members),
+            repairs,
+        }
+    }
+}
+
+impl<F: CoordFloat> Sanitize<F> for geo_types::Geometry<F> {
+    fn sanitize(&self) -> Sanitized<Self> {
+        fn wrap<G, F: CoordFloat>(
+            sanitized: Sanitized<G>,
+            variant: impl FnOnce(G) -> geo_types::Geometry<F>,
+        ) -> Sanitized<geo_types::Geometry<F>> {
+            Sanitized {
+                geometry: variant(sanitized.geometry),
+                repairs: sanitized.repairs,
+            }
+        }
+
+        match self {
+            geo_types::Geometry::LineString(line_string) => {
+                wrap(line_string.sanitize(), geo_types::Geometry::LineString)
+            }
+            geo_types::Geometry::Polygon(polygon) => {
+                wrap(polygon.sanitize(), geo_types::Geometry::Polygon)
+            }
+            geo_types::Geometry::MultiPoint(multi_point) => {
+                wrap(multi_point.sanitize(), geo_types::Geometry::MultiPoint)
+            }
+            geo_types::Geometry::MultiLineString(multi_line_string) => wrap(
+                multi_line_string.sanitize(),
+                geo_types::Geometry::MultiLineString,
+            ),
+            geo_types::Geometry::MultiPolygon(multi_polygon) => {
+                wrap(multi_polygon.sanitize(), geo_types::Geometry::MultiPolygon)
+            }
+            geo_types::Geometry::GeometryCollection(geometry_collection) => wrap(
+                geometry_collection.sanitize(),
+                geo_types::Geometry::GeometryCollection,
+            ),
+            geo_types::Geometry::Point(_)
+            | geo_types::Geometry::Line(_)
+            | geo_types::Geometry::Rect(_)
+            | geo_types::Geometry::Triangle(_) => unchanged(self),
+        }
+    }
+}
+
+fn unchanged<G: Clone>(geometry: &G) -> Sanitized<G> {
+    Sanitized {
+        geometry: geometry.clone(),
+        repairs: Vec::new(),
+    }
+}
+
+fn sanitize_members<F: CoordFloat, T: Sanitize<F>>(members: &[T]) -> (Vec<T>, Vec<Repair>) {
+    let mut repairs = Vec::new();
+    let mut sanitized_members = Vec::new();
+    for (index, member) in members.iter().enumerate() {
+        let sanitized = member.sanitize();
+        sanitized_members.push(sanitized.geometry);
+        repairs.extend(sanitized.repairs.into_iter().map(|mut repair| {
+            repair.path.insert(0, Location::Member(index));
+            repair
+        }));
+    }
+    (sanitized_members, repairs)
+}
+
+/// Returns the closed ring without its defects, or nothing if it encloses no area.
+fn sanitize_ring<F: CoordFloat>(
+    ring: &geo_types::LineString<F>,
+    location: Location,
+) -> (Option<geo_types::LineString<F>>, Vec<Repair>) {
+    let mut repairs = Vec::new();
+    // Polygons close their rings, so the last vertex repeats the first one.
+    let coords = ring.0.split_last().map_or(&[][..], |(_, coords)| coords);
+    let coords = clean_coords(coords.iter().copied().enumerate(), true, &mut repairs);
+
+    let ring = if coords.len() < 3 {
+        repairs.push(Repair {
+            path: Vec::new(),
+            kind: RepairKind::DegenerateRing,
+        });
+        None
+    } else {
+        let mut ring = geo_types::LineString(coords.into_iter().map(|(_, coord)| coord).collect());
+        ring.close();
+        Some(ring)
+    };
+    for repair in &mut repairs {
+        repair.path.insert(0, location);
+    }
+    (ring, repairs)
+}
+
+/// Removes vertices which are not finite, equal to the previous one or collinear with their neighbours.
+///
+/// Vertices come with their index in the input. The first and last vertices of cyclic sequences are neighbours.
+fn clean_coords<F: CoordFloat>(
+    coords: impl IntoIterator<Item = (usize, geo_types::Coord<F>)>,
+    cyclic: bool,
+    repairs: &mut Vec<Repair>,
+) -> Vec<(usize, geo_types::Coord<F>)> {
+    let mut removed = |index: usize, kind| {
+        repairs.push(Repair {
+            path: vec![Location::Vertex(index)],
+            kind,
+        })
+    };
+
+    let mut kept: Vec<(usize, geo_types::Coord<F>)> = Vec::new();
+    for (index, coord) in coords {
+        if !(coord.x.is_finite() && coord.y.is_finite()) {
+            removed(index, RepairKind::NonFiniteVertex);
+            continue;
+        }
+        if kept.last().is_some_and(|&(_, last)| last == coord) {
+            removed(index, RepairKind::DuplicateVertex);
+            continue;
+        }
+        while let [.., (_, previous), (middle_index, middle)] = kept[..] {
+            if !is_between(previous, middle, coord) {
+                break;
+            }
+            removed(middle_index, RepairKind::CollinearVertex);
+            kept.pop();
+        }
+        kept.push((index, coord));
+    }
+
+    while cyclic && kept.len() > 1 {
+        let (last_index, last) = kept[kept.len() - 1];
+        let (_, first) = kept[0];
+        if last == first {
+            removed(last_index, RepairKind::DuplicateVertex);
+            kept.pop();
+        } else if kept.len() > 2 && is_between(kept[kept.len() - 2].1, last, first) {
+            removed(last_index, RepairKind::CollinearVertex);
+            kept.pop();
+        } else if kept.len() > 2 && is_between(last, first, kept[1].1) {
+            removed(kept[0].0, RepairKind::CollinearVertex);
+            kept.remove(0);
+        } else {
+            break;
+        }
+    }
+    kept
+}
+
+/// Returns whether `middle` lies strictly between `start` and `end` on the line joining them.
+fn is_between<F: CoordFloat>(
+    start: geo_types::Coord<F>,
+    middle: geo_types::Coord<F>,
+    end: geo_types::Coord<F>,
+) -> bool {
+    let (first, second) = (middle - start, end - middle);
+    let cross = first.x * second.y - first.y * second.x;
+    let dot = first.x * second.x + first.y * second.y;
+    cross.is_zero() && dot > F::zero()
+}
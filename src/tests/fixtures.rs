@@ -4,21 +4,21 @@ use serde::{Deserialize, Serialize};
 #[allow(dead_code)]
 pub static POLYGONE_POLYLINE: Lazy<geo_types::GeometryCollection<f64>> = Lazy::new(|| {
     let data = include_str!("fixtures/polygon_polyline.json");
-    let feature_collection: FeatureCollection = serde_json::from_str(&data).unwrap();
+    let feature_collection: FeatureCollection = serde_json::from_str(data).unwrap();
     feature_collection.into()
 });
 
 #[allow(dead_code)]
 pub static DEMO: Lazy<geo_types::GeometryCollection<f64>> = Lazy::new(|| {
     let data = include_str!("fixtures/demo.json");
-    let feature_collection: FeatureCollection = serde_json::from_str(&data).unwrap();
+    let feature_collection: FeatureCollection = serde_json::from_str(data).unwrap();
     feature_collection.into()
 });
 
 #[allow(dead_code)]
 pub static DEMO_WITH_OFFSET: Lazy<geo_types::GeometryCollection<f64>> = Lazy::new(|| {
     let data = include_str!("fixtures/demo_with_offset.json");
-    let feature_collection: FeatureCollection = serde_json::from_str(&data).unwrap();
+    let feature_collection: FeatureCollection = serde_json::from_str(data).unwrap();
     feature_collection.into()
 });
 
@@ -97,7 +97,7 @@ impl From<Polygon> for geo_types::Polygon<f64> {
         Self::new(
             polygon
                 .coordinates
-                .get(0)
+                .first()
                 .cloned()
                 .unwrap_or_default()
                 .into_iter()
@@ -93,7 +93,9 @@ fn test_point_offset_targeting_segment_length() {
     let segment_length = radius * std::f64::consts::TAU / segments_expected as f64;
     let resolution = ArcResolution::SegmentLength(segment_length);
 
-    let result = point.offset_with_arc_resolution(radius, resolution).unwrap();
+    let result = point
+        .offset_with_arc_resolution(radius, resolution)
+        .unwrap();
     let expected = geo_types::MultiPolygon(vec![Polygon::new(
         LineString(Vec::from(POINT_OFFSET_COORDS)),
         Vec::new(),
@@ -178,7 +180,9 @@ fn test_polygon_with_hole_offset_high_resolution() {
 
 #[test]
 fn test_demo_offset() {
-    let result = fixtures::DEMO.offset_with_arc_resolution(0.0001, ArcResolution::SegmentCount(5)).unwrap();
+    let result = fixtures::DEMO
+        .offset_with_arc_resolution(0.0001, ArcResolution::SegmentCount(5))
+        .unwrap();
 
     println!(
         "{}",
@@ -191,3 +195,1280 @@ fn test_demo_offset() {
 
     // assert_eq!(*fixtures::DEMO_WITH_OFFSET, geo_types::GeometryCollection::from(fixtures::FeatureCollection::from(result)));
 }
+
+fn area(multi_polygon: &geo_types::MultiPolygon<f64>) -> f64 {
+    fn ring_area(ring: &LineString<f64>) -> f64 {
+        ring.lines()
+            .map(|line| line.start.x * line.end.y - line.end.x * line.start.y)
+            .sum::<f64>()
+            / 2.0
+    }
+
+    multi_polygon
+        .iter()
+        .map(|polygon| {
+            ring_area(polygon.exterior()).abs()
+                - polygon
+                    .interiors()
+                    .iter()
+                    .map(|interior| ring_area(interior).abs())
+                    .sum::<f64>()
+        })
+        .sum()
+}
+
+#[test]
+#[cfg(not(feature = "pure-rust"))]
+fn test_offset_with_scale_factor() {
+    use geo_types::polygon;
+    let polygon = polygon![
+        (x: 0., y: 0.),
+        (x: 0.001, y: 0.),
+        (x: 0.001, y: 0.001),
+        (x: 0., y: 0.001),
+    ];
+
+    let coarse = polygon.offset(0.0001).unwrap();
+    let options = OffsetOptions::new().with_scale_factor(1e9);
+    let fine = polygon.offset_with_options(0.0001, &options).unwrap();
+
+    // The default factor snaps coordinates to 0.001 units, which swallows the offset.
+    // The exact area is 1.44e-6 minus the corners cut by the arc approximation.
+    assert!((area(&coarse) - 1e-6).abs() < 1e-12);
+    assert!(area(&fine) > 1.4e-6 && area(&fine) < 1.44e-6);
+}
+
+#[test]
+fn test_offset_with_auto_scale_factor() {
+    use geo_types::polygon;
+    let polygon = polygon![
+        (x: 0., y: 0.),
+        (x: 0.001, y: 0.),
+        (x: 0.001, y: 0.001),
+        (x: 0., y: 0.001),
+    ];
+
+    let options = OffsetOptions::new().with_auto_scale_factor();
+    let result = polygon.offset_with_options(0.0001, &options).unwrap();
+    assert!(area(&result) > 1.4e-6 && area(&result) < 1.44e-6);
+
+    // Miters and circumscribed arcs reach further than the distance, which leaves less room for coordinates.
+    let line = geo_types::Line::new(Coord { x: 0., y: 0. }, Coord { x: 1., y: 0. });
+    let factor = |options: &OffsetOptions<f64>| options.resolve(&line, 1.0).unwrap().factor();
+    assert_eq!(factor(&options), 2f64.powi(52));
+    let miter = options
+        .clone()
+        .with_join_style(JoinStyle::Miter { limit: 4.0 });
+    assert_eq!(factor(&miter), 2f64.powi(50));
+    let circumscribed = options
+        .clone()
+        .with_arc_approximation(ArcApproximation::Circumscribed);
+    assert_eq!(factor(&circumscribed), 2f64.powi(51));
+
+    let result = fixtures::DEMO
+        .offset_with_options(0.0001, &options)
+        .unwrap();
+    let fine = OffsetOptions::new().with_scale_factor(1e9);
+    let expected = fixtures::DEMO.offset_with_options(0.0001, &fine).unwrap();
+    assert!((area(&result) - area(&expected)).abs() < area(&expected) * 1e-6);
+}
+
+#[test]
+fn test_polygon_offset_with_join_styles() {
+    use geo_types::polygon;
+    let polygon = polygon![
+        (x: -10., y: 10.),
+        (x: 10., y: 10.),
+        (x: 10., y: -10.),
+        (x: -10., y: -10.),
+    ];
+
+    let miter = OffsetOptions::new().with_join_style(JoinStyle::Miter { limit: 2.0 });
+    let result = polygon.offset_with_options(5.0, &miter).unwrap();
+    assert!((area(&result) - 900.0).abs() < 1e-6);
+
+    // A right angle has a miter ratio of sqrt(2).
+    let beveled_miter = OffsetOptions::new().with_join_style(JoinStyle::Miter { limit: 1.4 });
+    let result = polygon.offset_with_options(5.0, &beveled_miter).unwrap();
+    assert!((area(&result) - 850.0).abs() < 1e-6);
+
+    let bevel = OffsetOptions::new().with_join_style(JoinStyle::Bevel);
+    let result = polygon.offset_with_options(5.0, &bevel).unwrap();
+    assert!((area(&result) - 850.0).abs() < 1e-6);
+
+    let result = polygon.offset(5.0).unwrap();
+    assert!(area(&result) > 850.0 && area(&result) < 400.0 + 400.0 + 25.0 * std::f64::consts::PI);
+
+    let result = polygon.offset_with_options(-5.0, &miter).unwrap();
+    assert!((area(&result) - 100.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_line_string_offset_with_miter_join() {
+    let line_string = LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]);
+
+    let miter = OffsetOptions::new().with_join_style(JoinStyle::Miter { limit: 2.0 });
+    let result = line_string.offset_with_options(1.0, &miter).unwrap();
+
+    println!(
+        "{}",
+        result.to_svg().and(line_string.to_svg()).with_margin(5.0)
+    );
+    assert_eq!(result.0.len(), 1);
+    assert!(result.0[0]
+        .exterior()
+        .0
+        .iter()
+        .any(|coord| (coord.x - 11.0_f64).abs() < 1e-6 && (coord.y + 1.0_f64).abs() < 1e-6));
+}
+
+#[test]
+fn test_line_offset_with_cap_styles() {
+    let line = geo_types::Line::new(Coord { x: 0., y: 0. }, Coord { x: 0., y: 8. });
+
+    let flat = OffsetOptions::new().with_cap_style(CapStyle::Flat);
+    let result = line.offset_with_options(1.0, &flat).unwrap();
+    assert!((area(&result) - 16.0).abs() < 1e-9);
+
+    let square = OffsetOptions::new().with_cap_style(CapStyle::Square);
+    let result = line.offset_with_options(1.0, &square).unwrap();
+    assert!((area(&result) - 20.0).abs() < 1e-9);
+
+    let result = line.offset(1.0).unwrap();
+    assert!(area(&result) > 18.0 && area(&result) < 16.0 + std::f64::consts::PI);
+
+    let point = geo_types::Line::new(Coord { x: 0., y: 0. }, Coord { x: 0., y: 0. });
+    assert!(point.offset_with_options(1.0, &flat).unwrap().0.is_empty());
+    assert!((area(&point.offset_with_options(1.0, &square).unwrap()) - 4.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_line_string_offset_with_cap_styles() {
+    let line_string = LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]);
+    let options = OffsetOptions::new()
+        .with_cap_style(CapStyle::Flat)
+        .with_join_style(JoinStyle::Miter { limit: 2.0 });
+    let result = line_string.offset_with_options(1.0, &options).unwrap();
+    assert!((area(&result) - 40.0).abs() < 1e-6);
+
+    let multi_line_string = geo_types::MultiLineString(vec![
+        line_string,
+        LineString::from(vec![(20., 0.), (20., 10.)]),
+    ]);
+    let options = options.with_cap_style(CapStyle::Square);
+    let result = multi_line_string
+        .offset_with_options(1.0, &options)
+        .unwrap();
+    assert!((area(&result) - 44.0 - 24.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_line_string_offset_curve() {
+    let line_string = LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]);
+
+    let result = line_string.offset_curve(1.0).unwrap();
+    let expected =
+        geo_types::MultiLineString(vec![LineString::from(vec![(0., 1.), (9., 1.), (9., 10.)])]);
+    assert_eq!(expected, result);
+
+    let miter = OffsetOptions::new().with_join_style(JoinStyle::Miter { limit: 2.0 });
+    let result = line_string.offset_curve_with_options(-1.0, &miter).unwrap();
+    let expected = geo_types::MultiLineString(vec![LineString::from(vec![
+        (0., -1.),
+        (10., -1.),
+        (11., -1.),
+        (11., 0.),
+        (11., 10.),
+    ])]);
+    assert_eq!(expected, result);
+
+    let result = line_string.offset_curve(-1.0).unwrap();
+    assert_eq!(result.0.len(), 1);
+    assert_eq!(result.0[0].0.len(), 2 + 6); // The arc has five segments.
+}
+
+#[test]
+fn test_line_string_offset_curve_removes_loops() {
+    let line_string = LineString::from(vec![
+        (0., 0.),
+        (10., 0.),
+        (10., 1.),
+        (11., 1.),
+        (11., 0.),
+        (20., 0.),
+        (20., 10.),
+        (0., 10.),
+        (0., 0.),
+    ]);
+
+    for distance in [2.0_f64, -2.0, 4.0, -6.0] {
+        let result = line_string.offset_curve(distance).unwrap();
+        println!(
+            "{}",
+            result.to_svg().and(line_string.to_svg()).with_margin(5.0)
+        );
+        assert!(!result.0.is_empty());
+
+        for coord in result.0.iter().flat_map(|line_string| line_string.0.iter()) {
+            let closest = line_string
+                .lines()
+                .map(|line| {
+                    let direction = line.end - line.start;
+                    let t = ((coord.x - line.start.x) * direction.x
+                        + (coord.y - line.start.y) * direction.y)
+                        / (direction.x * direction.x + direction.y * direction.y);
+                    let projection = line.start + direction * t.clamp(0.0, 1.0);
+                    (coord.x - projection.x).hypot(coord.y - projection.y)
+                })
+                .fold(f64::INFINITY, f64::min);
+            assert!(closest > distance.abs() * 0.95, "{:?} is too close", coord);
+        }
+    }
+}
+
+#[test]
+fn test_line_string_single_sided_offset() {
+    let line_string = LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]);
+    let options = OffsetOptions::new().with_single_sided(true);
+
+    let result = line_string.offset_with_options(1.0, &options).unwrap();
+    assert!((area(&result) - 19.0).abs() < 1e-6);
+
+    let miter = options
+        .clone()
+        .with_join_style(JoinStyle::Miter { limit: 2.0 });
+    let result = line_string.offset_with_options(-1.0, &miter).unwrap();
+    assert!((area(&result) - 21.0).abs() < 1e-6);
+
+    let result = line_string.offset_with_options(-1.0, &options).unwrap();
+    println!(
+        "{}",
+        result.to_svg().and(line_string.to_svg()).with_margin(5.0)
+    );
+    assert!(area(&result) > 20.7 && area(&result) < 20.0 + std::f64::consts::FRAC_PI_4);
+
+    let line = geo_types::Line::new(Coord { x: 0., y: 0. }, Coord { x: 0., y: 8. });
+    let result = line.offset_with_options(-2.0, &options).unwrap();
+    assert!((area(&result) - 16.0).abs() < 1e-6);
+    assert!(result.0[0].exterior().0.contains(&Coord { x: 2., y: 8. }));
+
+    let polygon = Polygon::new(line_string, Vec::new());
+    assert_eq!(
+        polygon.offset_with_options(1.0, &options).unwrap(),
+        polygon.offset(1.0).unwrap()
+    );
+}
+
+#[test]
+fn test_polygon_offset_contour() {
+    use geo_types::polygon;
+    let polygon = polygon![
+        exterior: [
+        (x: -15., y: 15.),
+        (x: 15., y: 15.),
+        (x: 15., y: -15.),
+        (x: -15., y: -15.),],
+        interiors: [[
+            (x: -10., y: 10.),
+            (x: 10., y: 10.),
+            (x: 10., y: -10.),
+            (x: -10., y: -10.),
+    ]]];
+
+    let options = OffsetOptions::new().with_join_style(JoinStyle::Miter { limit: 2.0 });
+    let contour = polygon.offset_contour_with_options(1.0, &options).unwrap();
+    let ring_area = |ring: &LineString| area(&Polygon::new(ring.clone(), Vec::new()).into());
+
+    assert_eq!(contour.exteriors.0.len(), 1);
+    assert!(contour.exteriors.0[0].is_closed());
+    assert!((ring_area(&contour.exteriors.0[0]) - 32.0 * 32.0).abs() < 1e-6);
+
+    assert_eq!(contour.holes.0.len(), 1);
+    assert!(contour.holes.0[0].is_closed());
+    assert!((ring_area(&contour.holes.0[0]) - 18.0 * 18.0).abs() < 1e-6);
+
+    let clockwise_area = contour.holes.0[0]
+        .lines()
+        .map(|line| line.start.x * line.end.y - line.end.x * line.start.y)
+        .sum::<f64>();
+    assert!(clockwise_area < 0.0);
+
+    let rings = geo_types::MultiLineString::from(contour);
+    assert_eq!(rings.0.len(), 2);
+}
+
+#[test]
+#[cfg(all(feature = "clipper", feature = "pure-rust"))]
+fn test_offset_with_backends() {
+    let options = OffsetOptions::new().with_auto_scale_factor();
+    let clipper = options.clone().with_backend(ClipperBackend);
+    let geo = options.with_backend(GeoBackend);
+
+    let clipper_result = fixtures::DEMO
+        .offset_with_options(0.0001, &clipper)
+        .unwrap();
+    let geo_result = fixtures::DEMO.offset_with_options(0.0001, &geo).unwrap();
+    assert!((area(&clipper_result) - area(&geo_result)).abs() < area(&geo_result) * 1e-6);
+}
+
+#[test]
+#[cfg(feature = "clipper")]
+fn test_offset_with_clipper_algorithm() {
+    use geo_types::polygon;
+    let polygon = polygon![
+        exterior: [(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.)],
+        interiors: [[(x: 4., y: 4.), (x: 6., y: 4.), (x: 6., y: 6.), (x: 4., y: 6.)]],
+    ];
+    let options = OffsetOptions::new()
+        .with_arc_resolution(ArcResolution::SegmentLength(0.01))
+        .with_join_style(JoinStyle::Miter { limit: 2.0 });
+    let clipper = options.clone().with_algorithm(OffsetAlgorithm::Clipper);
+
+    let result = polygon.offset_with_options(1.0, &clipper).unwrap();
+    let expected = polygon.offset_with_options(1.0, &options).unwrap();
+    assert!((area(&result) - 144.0).abs() < 1e-6);
+    assert!((area(&result) - area(&expected)).abs() < 1e-6);
+
+    let result = polygon.offset_with_options(-1.0, &clipper).unwrap();
+    assert!((area(&result) - 48.0).abs() < 1e-6);
+
+    let line_string = LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]);
+    let result = line_string
+        .offset_with_algorithm(1.0, OffsetAlgorithm::Clipper)
+        .unwrap();
+    assert!(area(&result) > 40.0 && area(&result) < 40.0 + std::f64::consts::PI * 1.25);
+
+    let options = options.with_join_style(JoinStyle::Round);
+    let clipper = options.clone().with_algorithm(OffsetAlgorithm::Clipper);
+    let result = line_string.offset_with_options(1.0, &clipper).unwrap();
+    let expected = line_string.offset_with_options(1.0, &options).unwrap();
+    assert!((area(&result) - area(&expected)).abs() < 1e-2);
+
+    let options = options.with_cap_style(CapStyle::Flat);
+    let clipper = options.clone().with_algorithm(OffsetAlgorithm::Clipper);
+    let result = line_string.offset_with_options(1.0, &clipper).unwrap();
+    let expected = line_string.offset_with_options(1.0, &options).unwrap();
+    assert!((area(&result) - area(&expected)).abs() < 1e-2);
+
+    // Segments longer than half a circle leave arcs as coarse as possible.
+    let with_segment_length = |segment_length| {
+        OffsetOptions::new()
+            .with_arc_resolution(ArcResolution::SegmentLength(segment_length))
+            .with_algorithm(OffsetAlgorithm::Clipper)
+    };
+    assert_eq!(
+        line_string.offset_with_options(1.0, &with_segment_length(100.0)),
+        line_string.offset_with_options(1.0, &with_segment_length(std::f64::consts::PI))
+    );
+
+    let bevel = options.with_join_style(JoinStyle::Bevel);
+    assert!(matches!(
+        polygon.offset_with_options(1.0, &bevel.with_algorithm(OffsetAlgorithm::Clipper)),
+        Err(OffsetError::BackendFailure(_))
+    ));
+}
+
+#[test]
+#[cfg(feature = "clipper")]
+fn test_offset_outside_clipper_range() {
+    use geo_types::polygon;
+    let polygon = polygon![
+        (x: 1e7, y: 1e7),
+        (x: 1e7 + 10., y: 1e7),
+        (x: 1e7 + 10., y: 1e7 + 10.),
+        (x: 1e7, y: 1e7 + 10.),
+    ];
+    let line_string = LineString::from(vec![(1e7, 1e7), (1e7 + 10., 1e7)]);
+    // Scaled coordinates overflow `i64` with the first factor and exceed the range checked by Clipper with the
+    // second one.
+    for factor in [1e13, 5e11] {
+        let options = OffsetOptions::new()
+            .with_backend(ClipperBackend)
+            .with_scale_factor(factor);
+        assert!(matches!(
+            polygon.offset_with_options(1.0, &options),
+            Err(OffsetError::BackendFailure(_))
+        ));
+        let clipper = options.with_algorithm(OffsetAlgorithm::Clipper);
+        assert!(matches!(
+            polygon.offset_with_options(1.0, &clipper),
+            Err(OffsetError::BackendFailure(_))
+        ));
+        assert!(matches!(
+            line_string.offset_with_options(1.0, &clipper),
+            Err(OffsetError::BackendFailure(_))
+        ));
+    }
+}
+
+#[test]
+fn test_closed_line_string_offset() {
+    let ring = LineString::from(vec![(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]);
+    let options = OffsetOptions::new().with_arc_resolution(ArcResolution::SegmentLength(0.01));
+
+    let result = ring.offset_with_options(1.0, &options).unwrap();
+    assert_eq!(result.0.len(), 1);
+    assert_eq!(result.0[0].interiors().len(), 1);
+    assert!((area(&result) - 76.0 - std::f64::consts::PI).abs() < 1e-2);
+
+    // The inner side collapses, leaving no hole.
+    let result = ring.offset_with_options(6.0, &options).unwrap();
+    assert_eq!(result.0.len(), 1);
+    assert!(result.0[0].interiors().is_empty());
+    assert!((area(&result) - 484.0 + 36.0 * (4.0 - std::f64::consts::PI)).abs() < 1e-1);
+
+    let options = options.with_single_sided(true);
+    let result = ring.offset_with_options(1.0, &options).unwrap();
+    assert!((area(&result) - 36.0).abs() < 1e-6);
+    for distance in [5.5, 6.0] {
+        let result = ring.offset_with_options(distance, &options).unwrap();
+        assert!((area(&result) - 100.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_long_line_string_offset() {
+    let line_string: LineString<f64> = (0..10_000)
+        .map(|i| {
+            let t = i as f64;
+            (
+                t + (t * 0.37).sin() * 0.5,
+                (t * 0.001).sin() * 1000.0 + (t * 1.3).cos() * 0.5,
+            )
+        })
+        .collect();
+    let length: f64 = line_string
+        .lines()
+        .map(|line| line.dx().hypot(line.dy()))
+        .sum();
+
+    let result = line_string.offset(0.1).unwrap();
+    assert_eq!(result.0.len(), 1);
+    assert!((area(&result) - 0.2 * length).abs() < 0.01 * length);
+}
+
+#[test]
+fn test_concave_polygon_offset() {
+    use geo_types::polygon;
+    let polygon = polygon![
+        (x: 0., y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 5.),
+        (x: 5., y: 5.),
+        (x: 5., y: 10.),
+        (x: 0., y: 10.),
+    ];
+    let miter = OffsetOptions::new().with_join_style(JoinStyle::Miter { limit: 2.0 });
+
+    let result = polygon.offset_with_options(1.0, &miter).unwrap();
+    assert_eq!(result.0.len(), 1);
+    assert_eq!(result.0[0].exterior().0.len(), 7);
+    assert!((area(&result) - 119.0).abs() < 1e-6);
+
+    let result = polygon.offset_with_options(-1.0, &miter).unwrap();
+    assert!((area(&result) - 39.0).abs() < 1e-6);
+
+    let round = OffsetOptions::new().with_arc_resolution(ArcResolution::SegmentLength(0.01));
+    let result = polygon.offset_with_options(-1.0, &round).unwrap();
+    assert!((area(&result) - 40.0 + std::f64::consts::FRAC_PI_4).abs() < 1e-3);
+}
+
+#[test]
+fn test_polygon_offset_with_collapsing_rings() {
+    use geo_types::polygon;
+    let square = polygon![
+        (x: 0., y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+    ];
+    for distance in [-5.5, -6.0, -8.0] {
+        let result = square.offset(distance).unwrap();
+        assert!(result.0.is_empty(), "{:?}", result);
+    }
+
+    let polygon = polygon![
+        exterior: [
+            (x: -10., y: -10.),
+            (x: 20., y: -10.),
+            (x: 20., y: 20.),
+            (x: -10., y: 20.),
+        ],
+        interiors: [[
+            (x: 3., y: 3.),
+            (x: 3., y: 7.),
+            (x: 7., y: 7.),
+            (x: 7., y: 3.),
+        ]],
+    ];
+    let options = OffsetOptions::new().with_arc_resolution(ArcResolution::SegmentLength(0.01));
+    let result = polygon.offset_with_options(3.0, &options).unwrap();
+    assert_eq!(result.0.len(), 1);
+    assert!(result.0[0].interiors().is_empty());
+    assert!((area(&result) - 1296.0 + 9.0 * (4.0 - std::f64::consts::PI)).abs() < 1e-1);
+}
+
+#[test]
+fn test_multi_point_offset_with_many_points() {
+    let multi_point: geo_types::MultiPoint<f64> = (0..50)
+        .flat_map(|x| (0..50).map(move |y| geo_types::Point::new(x as f64, y as f64)))
+        .collect();
+    let single_point_area = area(&multi_point.0[0].offset(0.25).unwrap());
+
+    let result = multi_point.offset(0.25).unwrap();
+    assert_eq!(result.0.len(), 2500);
+    assert!((area(&result) / (2500.0 * single_point_area) - 1.0).abs() < 1e-3);
+
+    let result = multi_point.offset(0.75).unwrap();
+    assert_eq!(result.0.len(), 1);
+    assert_eq!(result.0[0].interiors().len(), 49 * 49);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_parallel_offset_matches_sequential() {
+    let multi_point: geo_types::MultiPoint<f64> = (0..20)
+        .flat_map(|x| (0..20).map(move |y| geo_types::Point::new(x as f64, (x * y) as f64 / 10.0)))
+        .collect();
+    let multi_line_string = geo_types::MultiLineString(vec![
+        LineString::from(vec![(0., 0.), (10., 0.), (10., 10.)]),
+        LineString::from(vec![(5., -5.), (5., 15.)]),
+        LineString::from(vec![(20., 20.), (30., 20.)]),
+    ]);
+    let collection =
+        geo_types::GeometryCollection(vec![multi_point.into(), multi_line_string.into()]);
+
+    let offset_with_threads = |thread_count| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(thread_count)
+            .build()
+            .unwrap()
+            .install(|| collection.offset(0.75).unwrap())
+    };
+    assert_eq!(offset_with_threads(4), offset_with_threads(1));
+}
+
+#[test]
+fn test_offset_unions_only_overlapping_parts() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug)]
+    struct Counting {
+        backend: std::sync::Arc<dyn OffsetBackend<f64>>,
+        count: AtomicUsize,
+    }
+
+    impl OffsetBackend<f64> for Counting {
+        fn union(
+            &self,
+            subject: &geo_types::MultiPolygon<f64>,
+            clip: &geo_types::MultiPolygon<f64>,
+            factor: f64,
+        ) -> Result<geo_types::MultiPolygon<f64>, OffsetError> {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            self.backend.union(subject, clip, factor)
+        }
+
+        fn difference(
+            &self,
+            subject: &geo_types::MultiPolygon<f64>,
+            clip: &geo_types::MultiPolygon<f64>,
+            factor: f64,
+        ) -> Result<geo_types::MultiPolygon<f64>, OffsetError> {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            self.backend.difference(subject, clip, factor)
+        }
+    }
+
+    let backend = std::sync::Arc::new(Counting {
+        backend: default_backend(),
+        count: AtomicUsize::new(0),
+    });
+    let options = OffsetOptions::new().with_backend(backend.clone());
+    let multi_point = geo_types::MultiPoint(vec![
+        geo_types::Point::new(0.0, 0.0),
+        geo_types::Point::new(10.0, 0.0),
+        geo_types::Point::new(0.0, 10.0),
+        geo_types::Point::new(0.5, 10.0),
+    ]);
+
+    let result = multi_point.offset_with_options(1.0, &options).unwrap();
+    assert_eq!(result.0.len(), 3);
+    assert_eq!(backend.count.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_offset_with_max_deviation() {
+    let options = OffsetOptions::new().with_arc_resolution(ArcResolution::MaxDeviation(1e-3));
+
+    for radius in [0.1_f64, 1.0, 100.0] {
+        let circle = geo_types::Point::new(0.0, 0.0)
+            .offset_with_options(radius, &options)
+            .unwrap();
+        for line in circle.0[0].exterior().lines() {
+            let middle = (line.start + line.end) / 2.0;
+            let deviation = radius - middle.x.hypot(middle.y);
+            assert!(deviation > 0.0 && deviation <= 1e-3 + 1e-9);
+        }
+    }
+
+    let line = geo_types::Line::new((0.0, 0.0), (10.0, 0.0));
+    let result = line.offset_with_options(2.0, &options).unwrap();
+    let capsule_area = 40.0 + std::f64::consts::PI * 4.0;
+    let deficit = capsule_area - area(&result);
+    assert!(deficit > 0.0 && deficit < std::f64::consts::TAU * 2.0 * 1e-3);
+}
+
+#[test]
+fn test_offset_with_angle_step() {
+    use geo_types::polygon;
+    let options = OffsetOptions::new()
+        .with_arc_resolution(ArcResolution::AngleStep(std::f64::consts::FRAC_PI_8));
+
+    let square = polygon![
+        (x: 0., y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+    ];
+    let result = square.offset_with_options(1.0, &options).unwrap();
+    assert_eq!(result.0[0].exterior().lines().count(), 4 + 4 * 4);
+
+    let (sin, cos) = 0.1_f64.sin_cos();
+    let rotated_square = polygon![
+        (x: 0., y: 0.),
+        (x: 10. * cos, y: 10. * sin),
+        (x: 10. * (cos - sin), y: 10. * (sin + cos)),
+        (x: -10. * sin, y: 10. * cos),
+    ];
+    let result = rotated_square.offset_with_options(1.0, &options).unwrap();
+    assert_eq!(result.0[0].exterior().lines().count(), 4 + 4 * 4);
+
+    let line = geo_types::Line::new((0.0, 0.0), (10.0, 0.0));
+    let result = line.offset_with_options(1.0, &options).unwrap();
+    assert_eq!(result.0[0].exterior().lines().count(), 2 + 2 * 8);
+
+    let circle = geo_types::Point::new(0.0, 0.0)
+        .offset_with_options(1.0, &options)
+        .unwrap();
+    assert_eq!(circle.0[0].exterior().lines().count(), 16);
+}
+
+#[test]
+fn test_offset_with_arc_segment_bounds() {
+    use geo_types::polygon;
+    let square = polygon![
+        (x: 0.0_f64, y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+    ];
+
+    let options = OffsetOptions::new()
+        .with_arc_resolution(ArcResolution::SegmentLength(10.0))
+        .with_arc_segment_bounds(3, 100);
+    let result = square.offset_with_options(1.0, &options).unwrap();
+    assert_eq!(result.0[0].exterior().lines().count(), 4 + 4 * 3);
+    assert!(result.0[0]
+        .exterior()
+        .coords()
+        .all(|coord| coord.x.is_finite() && coord.y.is_finite()));
+
+    let options = OffsetOptions::new()
+        .with_arc_resolution(ArcResolution::SegmentLength(1e-6))
+        .with_arc_segment_bounds(3, 100);
+    let result = square.offset_with_options(1e3, &options).unwrap();
+    assert_eq!(result.0[0].exterior().lines().count(), 4 + 4 * 100);
+    assert_eq!(options.arc_segment_bounds(), (3, 100));
+}
+
+#[test]
+fn test_offset_with_circumscribed_arcs() {
+    use geo_types::polygon;
+    let options = OffsetOptions::new().with_arc_approximation(ArcApproximation::Circumscribed);
+
+    let circle = geo_types::Point::new(1.0_f64, 2.0)
+        .offset_with_options(3.0, &options)
+        .unwrap();
+    for line in circle.0[0].exterior().lines() {
+        let middle = (line.start + line.end) / 2.0 - geo_types::Coord { x: 1.0, y: 2.0 };
+        assert!((middle.x.hypot(middle.y) - 3.0).abs() < 1e-9);
+    }
+
+    let square = polygon![
+        (x: 0.0_f64, y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+    ];
+    let rounded_square_area = 100.0 + 40.0 + std::f64::consts::PI;
+    let result = square.offset_with_options(1.0, &options).unwrap();
+    assert!(area(&result) > rounded_square_area);
+    let result = square.offset(1.0).unwrap();
+    assert!(area(&result) < rounded_square_area);
+
+    let line = geo_types::Line::new((0.0, 0.0), (10.0, 0.0));
+    let result = line.offset_with_options(1.0, &options).unwrap();
+    assert!(area(&result) > 20.0 + std::f64::consts::PI);
+    assert!(result.0[0]
+        .exterior()
+        .coords()
+        .all(|coord| coord.y.abs() <= 1.0 + 1e-9));
+}
+
+#[test]
+fn test_offset_with_anchored_arcs() {
+    use geo_types::polygon;
+    for arc_approximation in [ArcApproximation::Inscribed, ArcApproximation::Circumscribed] {
+        let options = OffsetOptions::new()
+            .with_arc_resolution(ArcResolution::SegmentCount(16))
+            .with_arc_approximation(arc_approximation)
+            .with_anchored_arcs(true);
+
+        let circle = geo_types::Point::new(0.0_f64, 0.0)
+            .offset_with_options(1.0, &options)
+            .unwrap();
+        let line = geo_types::Line::new((0.0, 0.0), (10.0, 0.0));
+        let result = line.offset_with_options(1.0, &options).unwrap();
+        let cap_coords: Vec<_> = result.0[0]
+            .exterior()
+            .coords()
+            .filter(|coord| coord.x < 0.0)
+            .collect();
+        assert!(!cap_coords.is_empty());
+        for coord in cap_coords {
+            let is_shared = |circle_coord: &geo_types::Coord<f64>| {
+                let difference = *circle_coord - *coord;
+                difference.x.hypot(difference.y) < 1e-12
+            };
+            assert!(circle.0[0].exterior().coords().any(is_shared));
+        }
+    }
+
+    let options = OffsetOptions::new()
+        .with_arc_resolution(ArcResolution::SegmentCount(16))
+        .with_anchored_arcs(true);
+    let square = polygon![
+        (x: 0.0_f64, y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+    ];
+    let result = square.offset_with_options(1.0, &options).unwrap();
+    assert_eq!(result.0[0].exterior().lines().count(), 4 + 4 * 4);
+    assert!(result.0[0]
+        .exterior()
+        .coords()
+        .any(|coord| *coord == geo_types::Coord { x: 11.0, y: 0.0 }));
+
+    // The grid would have more steps than signed integers can count.
+    let options = OffsetOptions::new()
+        .with_arc_resolution(ArcResolution::SegmentLength(4.2e-19))
+        .with_anchored_arcs(true);
+    let line_string = LineString::from(vec![(0.0, 0.0), (-1.0, -1e-4), (-2.0, 0.0)]);
+    let error = line_string
+        .offset_curve_with_options(1.0, &options)
+        .unwrap_err();
+    assert_eq!(error.cause(), &OffsetError::InfiniteSegmentCount);
+}
+
+#[test]
+fn test_offset_errors() {
+    use geo_types::polygon;
+    let square = polygon![
+        (x: 0.0_f64, y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+    ];
+
+    assert_eq!(square.offset(f64::NAN), Err(OffsetError::InvalidDistance));
+    assert_eq!(
+        square.offset(-f64::INFINITY),
+        Err(OffsetError::InvalidDistance)
+    );
+    let line_string = geo_types::LineString::from(vec![(0.0, 0.0), (f64::NAN, 1.0)]);
+    let non_finite_vertex = OffsetError::Located {
+        path: vec![Location::Vertex(1)],
+        error: Box::new(OffsetError::NonFiniteCoordinate),
+    };
+    assert_eq!(line_string.offset(1.0), Err(non_finite_vertex.clone()));
+    assert_eq!(line_string.offset_curve(1.0), Err(non_finite_vertex));
+
+    let with_resolution = |arc_resolution| OffsetOptions::new().with_arc_resolution(arc_resolution);
+    assert_eq!(
+        square.offset_with_options(1.0, &with_resolution(ArcResolution::SegmentCount(0))),
+        Err(OffsetError::ZeroSegmentCount)
+    );
+    assert_eq!(
+        square.offset_with_options(1.0, &with_resolution(ArcResolution::SegmentLength(0.0))),
+        Err(OffsetError::InfiniteSegmentCount)
+    );
+    assert_eq!(
+        geo_types::Point::new(0.0, 0.0).offset_with_options(
+            1e300,
+            &with_resolution(ArcResolution::SegmentLength(1e-300))
+        ),
+        Err(OffsetError::InfiniteSegmentCount)
+    );
+    assert_eq!(
+        geo_types::Point::new(0.0, 0.0)
+            .offset_with_options(1e6, &with_resolution(ArcResolution::SegmentLength(1e-9))),
+        Err(OffsetError::InfiniteSegmentCount)
+    );
+    assert_eq!(
+        square.offset_with_options(1.0, &with_resolution(ArcResolution::MaxDeviation(-1.0))),
+        Err(OffsetError::InvalidArcResolution)
+    );
+    assert_eq!(
+        square.offset_with_options(1.0, &with_resolution(ArcResolution::AngleStep(f64::NAN))),
+        Err(OffsetError::InvalidArcResolution)
+    );
+
+    #[derive(Debug)]
+    struct Failing;
+
+    impl OffsetBackend<f64> for Failing {
+        fn union(
+            &self,
+            _subject: &geo_types::MultiPolygon<f64>,
+            _clip: &geo_types::MultiPolygon<f64>,
+            _factor: f64,
+        ) -> Result<geo_types::MultiPolygon<f64>, OffsetError> {
+            Err(OffsetError::BackendFailure("unsupported".to_string()))
+        }
+
+        fn difference(
+            &self,
+            _subject: &geo_types::MultiPolygon<f64>,
+            _clip: &geo_types::MultiPolygon<f64>,
+            _factor: f64,
+        ) -> Result<geo_types::MultiPolygon<f64>, OffsetError> {
+            Err(OffsetError::BackendFailure("unsupported".to_string()))
+        }
+    }
+
+    let error = square
+        .offset_with_options(1.0, &OffsetOptions::new().with_backend(Failing))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the boolean operation failed: unsupported"
+    );
+    let error: Box<dyn std::error::Error> = OffsetError::from(EdgeError::VerticesOverlap).into();
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn test_offset_error_locations() {
+    use geo_types::polygon;
+    let square = polygon![
+        exterior: [(x: 0.0_f64, y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.)],
+        interiors: [[(x: 4., y: 4.), (x: 6., y: 4.), (x: 6., y: 6.), (x: 4., y: 6.)]],
+    ];
+    let mut broken = square.clone();
+    broken.interiors_mut(|interiors| interiors[0].0[2].y = f64::NAN);
+    let multi_polygon = geo_types::MultiPolygon(vec![square.clone(), broken]);
+    let error = multi_polygon.offset(1.0).unwrap_err();
+    assert_eq!(error.cause(), &OffsetError::NonFiniteCoordinate);
+    assert_eq!(
+        error.path(),
+        [
+            Location::Member(1),
+            Location::Interior(0),
+            Location::Vertex(2)
+        ]
+    );
+    let collection = geo_types::GeometryCollection(vec![
+        geo_types::Geometry::Point(geo_types::Point::new(0.0, 0.0)),
+        geo_types::Geometry::MultiPolygon(multi_polygon),
+    ]);
+    assert_eq!(
+        collection.offset(1.0).unwrap_err().path(),
+        [
+            Location::Member(1),
+            Location::Member(1),
+            Location::Interior(0),
+            Location::Vertex(2),
+        ]
+    );
+
+    // Edges too short for their direction to be computed are numbered as in the input, even when duplicate
+    // vertices are skipped and the ring is reversed.
+    let tiny = 1e-320;
+    let clockwise = geo_types::LineString::from(vec![
+        (0.0, 0.0),
+        (0.0, 10.0),
+        (0.0, 10.0),
+        (10.0, 10.0),
+        (10.0, tiny),
+        (10.0, 0.0),
+    ]);
+    let polygon = geo_types::Polygon::new(clockwise, vec![]);
+    let error = polygon.offset(1.0).unwrap_err();
+    assert_eq!(
+        error.cause(),
+        &OffsetError::EdgeError(EdgeError::VerticesOverlap)
+    );
+    assert_eq!(error.path(), [Location::Exterior, Location::Edge(4)]);
+    assert_eq!(
+        error.to_string(),
+        "invalid edge: the vertices of the edge overlap at exterior ring, edge 4"
+    );
+
+    let line_string =
+        geo_types::LineString::from(vec![(0.0, 0.0), (0.0, 0.0), (10.0, 0.0), (10.0, tiny)]);
+    assert_eq!(
+        line_string.offset_curve(1.0).unwrap_err().path(),
+        [Location::Edge(2)]
+    );
+    assert_eq!(
+        line_string.offset(1.0).unwrap_err().path(),
+        [Location::Edge(2)]
+    );
+}
+
+#[test]
+fn test_lenient_offset() {
+    use geo_types::polygon;
+    let square = polygon![
+        (x: 0.0_f64, y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+    ];
+    let far_square = polygon![
+        (x: 20.0_f64, y: 0.),
+        (x: 30., y: 0.),
+        (x: 30., y: 10.),
+        (x: 20., y: 10.),
+    ];
+    let sliver = polygon![(x: 40.0_f64, y: 0.), (x: 40., y: 1e-320), (x: 50., y: 0.)];
+    let multi_polygon = geo_types::MultiPolygon(vec![square, sliver, far_square]);
+    let collection = geo_types::GeometryCollection(vec![
+        geo_types::Geometry::Point(geo_types::Point::new(f64::NAN, 0.0)),
+        geo_types::Geometry::MultiPolygon(multi_polygon.clone()),
+    ]);
+
+    let options = OffsetOptions::new().with_lenient(true);
+    let error = multi_polygon.offset(1.0).unwrap_err();
+    assert_eq!(
+        error.path(),
+        [Location::Member(1), Location::Exterior, Location::Edge(0)]
+    );
+    let multi_polygon_with_offset = multi_polygon
+        .offset_with_diagnostics(1.0, &options)
+        .unwrap();
+    assert_eq!(multi_polygon_with_offset.offset.0.len(), 2);
+    assert_eq!(multi_polygon_with_offset.diagnostics, vec![error.clone()]);
+    assert_eq!(
+        multi_polygon.offset_with_options(1.0, &options),
+        Ok(multi_polygon_with_offset.offset.clone())
+    );
+
+    assert!(collection.offset(1.0).is_err());
+    let collection_with_offset = collection.offset_with_diagnostics(1.0, &options).unwrap();
+    assert_eq!(
+        collection_with_offset.offset,
+        multi_polygon_with_offset.offset
+    );
+    let paths: Vec<_> = collection_with_offset
+        .diagnostics
+        .iter()
+        .map(OffsetError::path)
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            &[Location::Member(0), Location::Vertex(0)][..],
+            &[
+                Location::Member(1),
+                Location::Member(1),
+                Location::Exterior,
+                Location::Edge(0),
+            ][..],
+        ]
+    );
+
+    // Errors which do not come from a member still fail the whole geometry.
+    assert_eq!(
+        collection.offset_with_diagnostics(f64::NAN, &options),
+        Err(OffsetError::InvalidDistance)
+    );
+}
+
+#[test]
+fn test_sanitized_offset() {
+    use geo_types::{line_string, polygon};
+    let ring = line_string![
+        (x: 0.0_f64, y: 0.),
+        (x: 5., y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: f64::NAN),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+        (x: 0., y: 5.),
+    ];
+    let square = polygon![
+        (x: 0.0_f64, y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+    ];
+    let sanitized = geo_types::Polygon::new(ring.clone(), vec![]).sanitize();
+    assert_eq!(sanitized.geometry, square);
+    let kinds: Vec<_> = sanitized
+        .repairs
+        .iter()
+        .map(|repair| (repair.path.clone(), repair.kind))
+        .collect();
+    let at_vertex = |index| vec![Location::Exterior, Location::Vertex(index)];
+    assert_eq!(
+        kinds,
+        [
+            (at_vertex(1), RepairKind::CollinearVertex),
+            (at_vertex(3), RepairKind::DuplicateVertex),
+            (at_vertex(4), RepairKind::NonFiniteVertex),
+            (at_vertex(7), RepairKind::CollinearVertex),
+        ]
+    );
+
+    // Degenerate members are emptied so that the other members keep their index.
+    let point_ring = line_string![(x: 1.0_f64, y: 1.), (x: 1., y: 1.), (x: 1., y: 1.)];
+    let multi_polygon = geo_types::MultiPolygon(vec![
+        geo_types::Polygon::new(point_ring, vec![]),
+        geo_types::Polygon::new(ring.clone(), vec![ring.clone()]),
+    ]);
+    let sanitized = multi_polygon.sanitize();
+    assert_eq!(sanitized.geometry.0[0], polygon![]);
+    assert_eq!(sanitized.geometry.0[1].interiors().len(), 1);
+    assert_eq!(
+        sanitized.repairs[1],
+        Repair {
+            path: vec![Location::Member(0), Location::Exterior],
+            kind: RepairKind::DegenerateRing,
+        }
+    );
+    assert_eq!(sanitized.repairs.len(), 10);
+
+    let line_string = line_string![
+        (x: 0.0_f64, y: 0.),
+        (x: 0., y: 0.),
+        (x: 1., y: 0.),
+        (x: 2., y: 0.),
+        (x: 2., y: 2.),
+    ];
+    assert_eq!(
+        line_string.sanitize().geometry,
+        line_string![(x: 0.0_f64, y: 0.), (x: 2., y: 0.), (x: 2., y: 2.)]
+    );
+
+    let options = OffsetOptions::new().with_sanitized_input(true);
+    for algorithm in [
+        OffsetAlgorithm::Union,
+        #[cfg(feature = "clipper")]
+        OffsetAlgorithm::Clipper,
+    ] {
+        let options = options.clone().with_algorithm(algorithm);
+        let unsanitized_options = options.clone().with_sanitized_input(false);
+        assert!(multi_polygon
+            .offset_with_options(1.0, &unsanitized_options)
+            .is_err());
+        let multi_polygon_with_offset = multi_polygon
+            .offset_with_diagnostics(1.0, &options)
+            .unwrap();
+        assert_eq!(multi_polygon_with_offset.repairs, sanitized.repairs);
+        assert_eq!(
+            multi_polygon.offset_with_options(1.0, &options),
+            Ok(multi_polygon_with_offset.offset.clone())
+        );
+        assert_eq!(
+            multi_polygon_with_offset.offset,
+            sanitized
+                .geometry
+                .offset_with_options(1.0, &options)
+                .unwrap()
+        );
+
+        // Points and lines cannot lose a vertex, so they are left unchanged and fail.
+        let point = geo_types::Point::new(f64::NAN, 0.0);
+        assert!(point.sanitize().repairs.is_empty());
+        let error = point.offset_with_options(1.0, &options).unwrap_err();
+        assert_eq!(error.cause(), &OffsetError::NonFiniteCoordinate);
+        assert_eq!(error.path(), [Location::Vertex(0)]);
+
+        let collection = geo_types::GeometryCollection(vec![
+            square.clone().into(),
+            point.into(),
+            geo_types::Line::new((0.0, 0.0), (f64::NAN, 1.0)).into(),
+        ]);
+        assert!(collection.sanitize().repairs.is_empty());
+        let error = collection.offset_with_options(1.0, &options).unwrap_err();
+        assert_eq!(error.path(), [Location::Member(1), Location::Vertex(0)]);
+        let lenient = options.clone().with_lenient(true);
+        let collection_with_offset = collection.offset_with_diagnostics(1.0, &lenient).unwrap();
+        assert_eq!(
+            collection_with_offset.offset,
+            square.offset_with_options(1.0, &options).unwrap()
+        );
+        assert!(collection_with_offset.repairs.is_empty());
+        let paths: Vec<_> = collection_with_offset
+            .diagnostics
+            .iter()
+            .map(OffsetError::path)
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                [Location::Member(1), Location::Vertex(0)],
+                [Location::Member(2), Location::Vertex(1)],
+            ]
+        );
+    }
+}
+
+#[test]
+fn test_validate() {
+    use geo_types::polygon;
+    let square = |min: f64, max: f64| {
+        geo_types::LineString::from(vec![(min, min), (max, min), (max, max), (min, max)])
+    };
+    let invalidity = |path: Vec<Location>, reason| Err(vec![Invalidity { path, reason }]);
+
+    let polygon = geo_types::Polygon::new(square(0.0, 10.0), vec![square(2.0, 4.0)]);
+    assert_eq!(polygon.validate(), Ok(()));
+    let empty = geo_types::Polygon::<f64>::new(geo_types::LineString(vec![]), vec![]);
+    assert_eq!(empty.validate(), Ok(()));
+    let touching_hole = polygon![
+        exterior: [(x: 0.0_f64, y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.)],
+        interiors: [[(x: 0., y: 5.), (x: 5., y: 4.), (x: 5., y: 6.)]],
+    ];
+    assert_eq!(touching_hole.validate(), Ok(()));
+
+    let spike = polygon![
+        (x: 0.0_f64, y: 0.),
+        (x: 10., y: 0.),
+        (x: 10., y: 10.),
+        (x: 10., y: 15.),
+        (x: 10., y: 10.),
+        (x: 0., y: 10.),
+    ];
+    assert_eq!(
+        spike.validate(),
+        invalidity(
+            vec![Location::Exterior, Location::Vertex(3)],
+            InvalidReason::Spike
+        )
+    );
+    let crossing_hole = geo_types::Polygon::new(
+        square(0.0, 10.0),
+        vec![geo_types::LineString::from(vec![
+            (8.0, 4.0),
+            (12.0, 4.0),
+            (12.0, 6.0),
+            (8.0, 6.0),
+        ])],
+    );
+    assert_eq!(
+        crossing_hole.validate(),
+        invalidity(
+            vec![Location::Interior(0), Location::Edge(0)],
+            InvalidReason::RingsIntersect {
+                ring: Location::Exterior,
+                edge: 1,
+            }
+        )
+    );
+    let outside_hole = geo_types::Polygon::new(square(0.0, 10.0), vec![square(20.0, 22.0)]);
+    assert_eq!(
+        outside_hole.validate(),
+        invalidity(vec![Location::Interior(0)], InvalidReason::HoleOutsideShell)
+    );
+    let nested_holes =
+        geo_types::Polygon::new(square(0.0, 10.0), vec![square(2.0, 8.0), square(4.0, 6.0)]);
+    assert_eq!(
+        nested_holes.validate(),
+        invalidity(
+            vec![Location::Interior(1)],
+            InvalidReason::NestedHole { interior: 0 }
+        )
+    );
+
+    // Polygons may lie in the holes of others, but not overlap them.
+    let island = geo_types::Polygon::new(square(2.5, 3.5), vec![]);
+    let multi_polygon = geo_types::MultiPolygon(vec![nested_holes.clone(), island.clone()]);
+    let valid = geo_types::MultiPolygon(vec![polygon.clone(), island]);
+    assert_eq!(valid.validate(), Ok(()));
+    let overlapping = geo_types::MultiPolygon(vec![
+        polygon.clone(),
+        geo_types::Polygon::new(square(8.0, 12.0), vec![]),
+        geo_types::Polygon::new(square(-5.0, 15.0), vec![]),
+    ]);
+    assert_eq!(
+        overlapping.validate().unwrap_err(),
+        [
+            Invalidity {
+                path: vec![Location::Member(1)],
+                reason: InvalidReason::OverlappingPolygons { member: 0 },
+            },
+            Invalidity {
+                path: vec![Location::Member(2)],
+                reason: InvalidReason::OverlappingPolygons { member: 0 },
+            },
+        ]
+    );
+
+    // Polygons may share edges from opposite sides, but not from the same side.
+    let right_square =
+        polygon![(x: 10., y: 0.), (x: 20., y: 0.), (x: 20., y: 10.), (x: 10., y: 10.)];
+    let adjacent = geo_types::MultiPolygon(vec![polygon.clone(), right_square]);
+    assert_eq!(adjacent.validate(), Ok(()));
+    let lower_half = polygon![(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 5.), (x: 0., y: 5.)];
+    let inscribed = polygon![(x: 5., y: 0.), (x: 10., y: 5.), (x: 0., y: 5.)];
+    for inner in [polygon.clone(), lower_half, inscribed] {
+        let stacked = geo_types::MultiPolygon(vec![polygon.clone(), inner]);
+        assert_eq!(
+            stacked.validate(),
+            invalidity(
+                vec![Location::Member(1)],
+                InvalidReason::OverlappingPolygons { member: 0 }
+            )
+        );
+    }
+
+    let options = OffsetOptions::new().with_validated_input(true);
+    assert!(adjacent.offset_with_options(1.0, &options).is_ok());
+    assert!(spike.offset_with_options(1.0, &options).is_err());
+    assert!(valid.offset_with_options(1.0, &options).is_ok());
+    let error = multi_polygon
+        .offset_with_options(1.0, &options)
+        .unwrap_err();
+    assert_eq!(error.path(), [Location::Member(0), Location::Interior(1)]);
+    assert_eq!(
+        error.to_string(),
+        "the geometry is invalid: the interior ring lies inside interior ring 0 at member 0, interior ring 1"
+    );
+    let lenient_options = options.clone().with_lenient(true);
+    let multi_polygon_with_offset = multi_polygon
+        .offset_with_diagnostics(1.0, &lenient_options)
+        .unwrap();
+    assert_eq!(multi_polygon_with_offset.diagnostics, vec![error]);
+    assert_eq!(multi_polygon_with_offset.offset.0.len(), 1);
+    let overlapping_with_offset = overlapping
+        .offset_with_diagnostics(1.0, &lenient_options)
+        .unwrap();
+    assert_eq!(
+        overlapping_with_offset.diagnostics,
+        [1, 2].map(|member| OffsetError::Located {
+            path: vec![Location::Member(member)],
+            error: Box::new(OffsetError::InvalidGeometry(
+                InvalidReason::OverlappingPolygons { member: 0 }
+            )),
+        })
+    );
+    assert_eq!(
+        overlapping_with_offset.offset,
+        overlapping.0[0].offset_with_options(1.0, &options).unwrap()
+    );
+}
@@ -0,0 +1,563 @@
+//! Validity of polygons following the rules of the OGC Simple Features specification.
+//!
+//! Offsets of invalid polygons are meaningless, since rings which cross themselves or each other do not bound a
+//! well-defined area. Polygons can be checked with [`Validate::validate`], or before being offset through
+//! [`crate::OffsetOptions::with_validated_input`].
+
+use crate::offset::distinct_edges;
+use crate::{Edge, Location, OffsetError};
+use geo_types::CoordFloat;
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{Envelope, RTree, AABB};
+
+/// Rule of the OGC Simple Features specification broken by a polygon.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InvalidReason {
+    /// A coordinate of the ring is not a finite number.
+    NonFiniteCoordinate,
+    /// The ring has fewer than three distinct vertices, so it encloses no area.
+    TooFewVertices,
+    /// The ring turns back on itself at the vertex, forming a spike of zero width.
+    Spike,
+    /// The edge crosses or touches another edge of the same ring, at the given index.
+    SelfIntersection { edge: usize },
+    /// The edge crosses or overlaps an edge of another ring of the same polygon.
+    RingsIntersect { ring: Location, edge: usize },
+    /// The interior ring lies outside the exterior ring.
+    HoleOutsideShell,
+    /// The interior ring lies inside another interior ring, at the given index.
+    NestedHole { interior: usize },
+    /// The polygon overlaps another polygon of the same multi-polygon, at the given index.
+    OverlappingPolygons { member: usize },
+}
+
+impl std::fmt::Display for InvalidReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NonFiniteCoordinate => write!(f, "a coordinate is not finite"),
+            Self::TooFewVertices => write!(f, "the ring has fewer than three distinct vertices"),
+            Self::Spike => write!(f, "the ring turns back on itself"),
+            Self::SelfIntersection { edge } => {
+                write!(f, "the ring intersects itself at edge {}", edge)
+            }
+            Self::RingsIntersect { ring, edge } => {
+                write!(f, "the ring intersects the {}, edge {}", ring, edge)
+            }
+            Self::HoleOutsideShell => write!(f, "the interior ring lies outside the exterior ring"),
+            Self::NestedHole { interior } => {
+                write!(
+                    f,
+                    "the interior ring lies inside interior ring {}",
+                    interior
+                )
+            }
+            Self::OverlappingPolygons { member } => {
+                write!(f, "the polygon overlaps member {}", member)
+            }
+        }
+    }
+}
+
+/// Rule broken by a part of a geometry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Invalidity {
+    /// Parts leading to the invalid ring, vertex or edge, from the outermost one.
+    pub path: Vec<Location>,
+    /// The rule which is broken.
+    pub reason: InvalidReason,
+}
+
+impl From<Invalidity> for OffsetError {
+    fn from(invalidity: Invalidity) -> Self {
+        OffsetError::InvalidGeometry(invalidity.reason).within(invalidity.path)
+    }
+}
+
+/// Checks that polygons follow the rules of the OGC Simple Features specification.
+///
+/// Rings must be simple, holes must lie inside the exterior without nesting, and the polygons of a multi-polygon
+/// must not overlap, though they may share edges. Rings may touch each other at single points. Empty polygons are
+/// valid.
+///
+/// ```
+/// # use geo_offset::{InvalidReason, Invalidity, Location, Validate};
+/// # use geo_types::polygon;
+/// let bowtie = polygon![(x: 0., y: 0.), (x: 2., y: 2.), (x: 2., y: 0.), (x: 0., y: 2.)];
+/// assert_eq!(
+///     bowtie.validate(),
+///     Err(vec![Invalidity {
+///         path: vec![Location::Exterior, Location::Edge(0)],
+///         reason: InvalidReason::SelfIntersection { edge: 2 },
+///     }])
+/// );
+/// ```
+pub trait Validate<F: CoordFloat> {
+    /// Returns every rule broken by the geometry, in the order of its parts.
+    fn validate(&self) -> Result<(), Vec<Invalidity>>;
+}
+
+impl<F: CoordFloat> Validate<F> for geo_types::Polygon<F> {
+    fn validate(&self) -> Result<(), Vec<Invalidity>> {
+        into_result(polygon_invalidities(self))
+    }
+}
+
+impl<F: CoordFloat> Validate<F> for geo_types::MultiPolygon<F> {
+    fn validate(&self) -> Result<(), Vec<Invalidity>> {
+        let mut invalidities = Vec::new();
+        for (index, polygon) in self.0.iter().enumerate() {
+            invalidities.extend(located(
+                polygon_invalidities(polygon),
+                Location::Member(index),
+            ));
+        }
+        if invalidities.is_empty() {
+            invalidities = overlapping_polygons(&self.0);
+        }
+        into_result(invalidities)
+    }
+}
+
+fn into_result(invalidities: Vec<Invalidity>) -> Result<(), Vec<Invalidity>> {
+    if invalidities.is_empty() {
+        Ok(())
+    } else {
+        Err(invalidities)
+    }
+}
+
+fn located(invalidities: Vec<Invalidity>, location: Location) -> impl Iterator<Item = Invalidity> {
+    invalidities.into_iter().map(move |mut invalidity| {
+        invalidity.path.insert(0, location);
+        invalidity
+    })
+}
+
+/// Ring of a polygon, without its closing vertex and repeated vertices.
+struct Ring<F: CoordFloat> {
+    location: Location,
+    coords: Vec<geo_types::Coord<F>>,
+    edges: Vec<Edge<F>>,
+    /// Index of each edge in the input ring.
+    edge_indices: Vec<usize>,
+}
+
+fn polygon_invalidities<F: CoordFloat>(polygon: &geo_types::Polygon<F>) -> Vec<Invalidity> {
+    let rings = match polygon_rings(polygon) {
+        Ok(rings) => rings,
+        Err(invalidities) => return invalidities,
+    };
+    let (exterior, interiors) = match rings.split_first() {
+        Some(rings) => rings,
+        None => return Vec::new(),
+    };
+
+    let mut invalidities = Vec::new();
+    for (index, interior) in interiors.iter().enumerate() {
+        let crossing = std::iter::once(exterior)
+            .chain(&interiors[..index])
+            .find_map(|ring| rings_crossing(interior, ring));
+        let enclosing_interior = || {
+            (0..interiors.len()).find(|&other| {
+                other != index
+                    && ring_inside(interior, &interiors[other])
+                    && rings_crossing(interior, &interiors[other]).is_none()
+            })
+        };
+        if let Some(invalidity) = crossing {
+            invalidities.push(invalidity);
+        } else if interior
+            .coords
+            .iter()
+            .any(|coord| locate(coord, &exterior.coords) == Side::Outside)
+        {
+            invalidities.push(Invalidity {
+                path: vec![interior.location],
+                reason: InvalidReason::HoleOutsideShell,
+            });
+        } else if let Some(other) = enclosing_interior() {
+            invalidities.push(Invalidity {
+                path: vec![interior.location],
+                reason: InvalidReason::NestedHole { interior: other },
+            });
+        }
+    }
+    invalidities
+}
+
+/// Returns the rings of the polygon, exterior first, or the rules they break on their own.
+///
+/// Empty polygons have no rings.
+fn polygon_rings<F: CoordFloat>(
+    polygon: &geo_types::Polygon<F>,
+) -> Result<Vec<Ring<F>>, Vec<Invalidity>> {
+    if polygon.exterior().0.is_empty() && polygon.interiors().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let locations = std::iter::once(Location::Exterior)
+        .chain((0..polygon.interiors().len()).map(Location::Interior));
+    let rings = std::iter::once(polygon.exterior()).chain(polygon.interiors());
+    let mut valid_rings = Vec::new();
+    let mut invalidities = Vec::new();
+    for (location, ring) in locations.zip(rings) {
+        match ring_with_location(ring, location) {
+            Ok(ring) => valid_rings.push(ring),
+            Err(ring_invalidities) => {
+                invalidities.extend(located(ring_invalidities, location));
+            }
+        }
+    }
+    if invalidities.is_empty() {
+        Ok(valid_rings)
+    } else {
+        Err(invalidities)
+    }
+}
+
+/// Returns the ring without repeated vertices, or the rules it breaks on its own.
+fn ring_with_location<F: CoordFloat>(
+    ring: &geo_types::LineString<F>,
+    location: Location,
+) -> Result<Ring<F>, Vec<Invalidity>> {
+    let at = |location, reason| Invalidity {
+        path: vec![location],
+        reason,
+    };
+
+    if let Some(index) = ring
+        .0
+        .iter()
+        .position(|coord| !(coord.x.is_finite() && coord.y.is_finite()))
+    {
+        return Err(vec![at(
+            Location::Vertex(index),
+            InvalidReason::NonFiniteCoordinate,
+        )]);
+    }
+    let (mut coords, edges, edge_indices) = distinct_edges(&ring.0);
+    // Polygons close their rings, so the first vertex is repeated at the end.
+    coords.pop();
+    if coords.len() < 3 {
+        return Err(vec![Invalidity {
+            path: Vec::new(),
+            reason: InvalidReason::TooFewVertices,
+        }]);
+    }
+
+    let mut invalidities = Vec::new();
+    for (index, edge) in edges.iter().enumerate() {
+        let next_index = (index + 1) % edges.len();
+        if is_spike(edge, &edges[next_index]) {
+            let vertex = edge_indices[next_index];
+            invalidities.push(at(Location::Vertex(vertex), InvalidReason::Spike));
+        }
+    }
+    if invalidities.is_empty() {
+        for (first, second) in candidate_pairs(&edges, &edges) {
+            let adjacent = second == first + 1 || (first == 0 && second == edges.len() - 1);
+            if first < second && !adjacent && segments_touch(&edges[first], &edges[second]) {
+                invalidities.push(at(
+                    Location::Edge(edge_indices[first]),
+                    InvalidReason::SelfIntersection {
+                        edge: edge_indices[second],
+                    },
+                ));
+            }
+        }
+    }
+
+    if invalidities.is_empty() {
+        Ok(Ring {
+            location,
+            coords,
+            edges,
+            edge_indices,
+        })
+    } else {
+        Err(invalidities)
+    }
+}
+
+/// Returns whether the ring turns back at the vertex between the edges.
+fn is_spike<F: CoordFloat>(edge: &Edge<F>, next_edge: &Edge<F>) -> bool {
+    let (first, second) = (edge.next - edge.current, next_edge.next - next_edge.current);
+    let cross = first.x * second.y - first.y * second.x;
+    let dot = first.x * second.x + first.y * second.y;
+    cross.is_zero() && dot < F::zero()
+}
+
+/// Returns where `ring` crosses `other`, or overlaps it along an edge.
+fn rings_crossing<F: CoordFloat>(ring: &Ring<F>, other: &Ring<F>) -> Option<Invalidity> {
+    candidate_pairs(&ring.edges, &other.edges)
+        .into_iter()
+        .find(|&(edge, other_edge)| segments_cross(&ring.edges[edge], &other.edges[other_edge]))
+        .map(|(edge, other_edge)| Invalidity {
+            path: vec![ring.location, Location::Edge(ring.edge_indices[edge])],
+            reason: InvalidReason::RingsIntersect {
+                ring: other.location,
+                edge: other.edge_indices[other_edge],
+            },
+        })
+}
+
+/// Returns whether `ring` lies inside `other`, given that they do not cross.
+fn ring_inside<F: CoordFloat>(ring: &Ring<F>, other: &Ring<F>) -> bool {
+    ring.coords
+        .iter()
+        .any(|coord| locate(coord, &other.coords) == Side::Inside)
+}
+
+/// Returns the polygons which overlap an earlier one.
+///
+/// Polygons with rings breaking rules on their own are left out.
+pub(crate) fn overlapping_polygons<F: CoordFloat>(
+    polygons: &[geo_types::Polygon<F>],
+) -> Vec<Invalidity> {
+    let rings: Vec<Vec<Ring<F>>> = polygons
+        .iter()
+        .map(|polygon| polygon_rings(polygon).unwrap_or_default())
+        .collect();
+    let envelopes: Vec<_> = rings
+        .iter()
+        .map(|rings| {
+            let (first, rest) = rings.first()?.coords.split_first()?;
+            Some(
+                rest.iter()
+                    .fold(AABB::from_point(to_f64(first)), |envelope, coord| {
+                        envelope.merged(&AABB::from_point(to_f64(coord)))
+                    }),
+            )
+        })
+        .collect();
+    let tree = RTree::bulk_load(
+        envelopes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, envelope)| {
+                envelope.map(|envelope| GeomWithData::new(Rectangle::from_aabb(envelope), index))
+            })
+            .collect(),
+    );
+
+    let mut invalidities = Vec::new();
+    for (index, envelope) in envelopes.iter().enumerate() {
+        let envelope = match envelope {
+            Some(envelope) => envelope,
+            None => continue,
+        };
+        let mut members: Vec<_> = tree
+            .locate_in_envelope_intersecting(envelope)
+            .map(|neighbour| neighbour.data)
+            .filter(|&member| member < index)
+            .collect();
+        members.sort_unstable();
+        let overlapping = members
+            .into_iter()
+            .find(|&member| polygons_overlap(&rings[index], &rings[member]));
+        if let Some(member) = overlapping {
+            invalidities.push(Invalidity {
+                path: vec![Location::Member(index)],
+                reason: InvalidReason::OverlappingPolygons { member },
+            });
+        }
+    }
+    invalidities
+}
+
+/// Returns whether the interiors of the polygons bounded by `rings` and `other`, exterior first, share an area.
+///
+/// Polygons sharing edges from opposite sides do not overlap.
+fn polygons_overlap<F: CoordFloat>(rings: &[Ring<F>], other: &[Ring<F>]) -> bool {
+    let two = F::one() + F::one();
+    // Exteriors without crossing or shared edges overlap where one has a vertex or an edge inside the other.
+    let probes = |rings: &[Ring<F>]| {
+        rings[0]
+            .edges
+            .iter()
+            .flat_map(move |edge| [edge.current, (edge.current + edge.next) / two])
+            .collect::<Vec<_>>()
+    };
+    rings.iter().any(|ring| {
+        other
+            .iter()
+            .any(|other_ring| rings_overlap(ring, other_ring))
+    }) || probes(rings)
+        .iter()
+        .any(|coord| inside_polygon(coord, other))
+        || probes(other)
+            .iter()
+            .any(|coord| inside_polygon(coord, rings))
+}
+
+/// Returns whether `ring` crosses `other`, or shares an edge with it with the interiors of both polygons on the same
+/// side.
+fn rings_overlap<F: CoordFloat>(ring: &Ring<F>, other: &Ring<F>) -> bool {
+    // The interior of a polygon lies on the left of its counter-clockwise exterior and clockwise interiors.
+    let interior_on_left = |ring: &Ring<F>| {
+        let counter_clockwise = ring
+            .edges
+            .iter()
+            .map(|edge| orientation(&ring.coords[0], &edge.current, &edge.next))
+            .fold(F::zero(), |area, triangle| area + triangle)
+            > F::zero();
+        counter_clockwise == (ring.location == Location::Exterior)
+    };
+    let same_side = interior_on_left(ring) == interior_on_left(other);
+    candidate_pairs(&ring.edges, &other.edges)
+        .into_iter()
+        .any(|(edge, other_edge)| {
+            let (edge, other_edge) = (&ring.edges[edge], &other.edges[other_edge]);
+            if is_collinear(edge, other_edge) {
+                let direction = edge.next - edge.current;
+                let other_direction = other_edge.next - other_edge.current;
+                let same_direction =
+                    direction.x * other_direction.x + direction.y * other_direction.y > F::zero();
+                same_direction == same_side && collinear_overlap(edge, other_edge)
+            } else {
+                segments_cross(edge, other_edge)
+            }
+        })
+}
+
+/// Returns whether `coord` lies strictly inside the polygon bounded by `rings`, exterior first.
+fn inside_polygon<F: CoordFloat>(coord: &geo_types::Coord<F>, rings: &[Ring<F>]) -> bool {
+    locate(coord, &rings[0].coords) == Side::Inside
+        && rings[1..]
+            .iter()
+            .all(|ring| locate(coord, &ring.coords) == Side::Outside)
+}
+
+/// Position of a point relative to a ring.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Side {
+    Inside,
+    Outside,
+    Boundary,
+}
+
+/// Returns where `coord` lies relative to the ring through `coords`, which is implicitly closed.
+fn locate<F: CoordFloat>(coord: &geo_types::Coord<F>, coords: &[geo_types::Coord<F>]) -> Side {
+    let mut inside = false;
+    for (index, start) in coords.iter().enumerate() {
+        let end = &coords[(index + 1) % coords.len()];
+        if orientation(start, end, coord).is_zero() && within_box(start, end, coord) {
+            return Side::Boundary;
+        }
+        if (start.y > coord.y) != (end.y > coord.y) {
+            let x = start.x + (coord.y - start.y) / (end.y - start.y) * (end.x - start.x);
+            if coord.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    if inside {
+        Side::Inside
+    } else {
+        Side::Outside
+    }
+}
+
+/// Returns twice the signed area of the triangle, positive if it turns counter-clockwise.
+fn orientation<F: CoordFloat>(
+    a: &geo_types::Coord<F>,
+    b: &geo_types::Coord<F>,
+    c: &geo_types::Coord<F>,
+) -> F {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Returns whether `coord` lies within the bounding box of `start` and `end`.
+fn within_box<F: CoordFloat>(
+    start: &geo_types::Coord<F>,
+    end: &geo_types::Coord<F>,
+    coord: &geo_types::Coord<F>,
+) -> bool {
+    start.x.min(end.x) <= coord.x
+        && coord.x <= start.x.max(end.x)
+        && start.y.min(end.y) <= coord.y
+        && coord.y <= start.y.max(end.y)
+}
+
+/// Returns whether the edges share at least one point.
+fn segments_touch<F: CoordFloat>(edge: &Edge<F>, other: &Edge<F>) -> bool {
+    let sides = [
+        orientation(&other.current, &other.next, &edge.current),
+        orientation(&other.current, &other.next, &edge.next),
+        orientation(&edge.current, &edge.next, &other.current),
+        orientation(&edge.current, &edge.next, &other.next),
+    ];
+    let opposite = |first: F, second: F| {
+        (first > F::zero() && second < F::zero()) || (first < F::zero() && second > F::zero())
+    };
+    (opposite(sides[0], sides[1]) && opposite(sides[2], sides[3]))
+        || (sides[0].is_zero() && within_box(&other.current, &other.next, &edge.current))
+        || (sides[1].is_zero() && within_box(&other.current, &other.next, &edge.next))
+        || (sides[2].is_zero() && within_box(&edge.current, &edge.next, &other.current))
+        || (sides[3].is_zero() && within_box(&edge.current, &edge.next, &other.next))
+}
+
+/// Returns whether the edges cross each other, or overlap along a part of positive length.
+fn segments_cross<F: CoordFloat>(edge: &Edge<F>, other: &Edge<F>) -> bool {
+    let sides = [
+        orientation(&other.current, &other.next, &edge.current),
+        orientation(&other.current, &other.next, &edge.next),
+        orientation(&edge.current, &edge.next, &other.current),
+        orientation(&edge.current, &edge.next, &other.next),
+    ];
+    if sides.iter().all(|side| side.is_zero()) {
+        return collinear_overlap(edge, other);
+    }
+    sides[0] * sides[1] < F::zero() && sides[2] * sides[3] < F::zero()
+}
+
+/// Returns whether the edges lie on the same line.
+fn is_collinear<F: CoordFloat>(edge: &Edge<F>, other: &Edge<F>) -> bool {
+    orientation(&other.current, &other.next, &edge.current).is_zero()
+        && orientation(&other.current, &other.next, &edge.next).is_zero()
+}
+
+/// Returns whether collinear edges overlap along a part of positive length.
+fn collinear_overlap<F: CoordFloat>(edge: &Edge<F>, other: &Edge<F>) -> bool {
+    // Collinear edges overlap if their projections on the main axis do.
+    let direction = edge.next - edge.current;
+    let project = |coord: geo_types::Coord<F>| {
+        if direction.x.abs() >= direction.y.abs() {
+            coord.x
+        } else {
+            coord.y
+        }
+    };
+    let (start, end) = (project(edge.current), project(edge.next));
+    let (other_start, other_end) = (project(other.current), project(other.next));
+    start.max(end).min(other_start.max(other_end)) > start.min(end).max(other_start.min(other_end))
+}
+
+/// Returns the pairs of edges of `edges` and `others` whose bounding boxes intersect.
+fn candidate_pairs<F: CoordFloat>(edges: &[Edge<F>], others: &[Edge<F>]) -> Vec<(usize, usize)> {
+    let envelope = |edge: &Edge<F>| AABB::from_corners(to_f64(&edge.current), to_f64(&edge.next));
+    let tree = RTree::bulk_load(
+        others
+            .iter()
+            .enumerate()
+            .map(|(index, edge)| GeomWithData::new(Rectangle::from_aabb(envelope(edge)), index))
+            .collect(),
+    );
+    let mut pairs: Vec<_> = edges
+        .iter()
+        .enumerate()
+        .flat_map(|(index, edge)| {
+            tree.locate_in_envelope_intersecting(&envelope(edge))
+                .map(move |other| (index, other.data))
+        })
+        .collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+fn to_f64<F: CoordFloat>(coord: &geo_types::Coord<F>) -> [f64; 2] {
+    [
+        coord.x.to_f64().unwrap_or(f64::NAN),
+        coord.y.to_f64().unwrap_or(f64::NAN),
+    ]
+}